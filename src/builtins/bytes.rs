@@ -0,0 +1,130 @@
+use crate::{
+	numeric::Numeric,
+	runtime::{error::RuntimeError, value::RuntimeValue},
+};
+
+use super::{expect_args, expect_string, BuiltinResult, Member, Namespace};
+
+/// Byte buffer functions, also callable as methods: `b.len()` is
+/// `bytes.len(b)`. Strings are turned into bytes with `s.encode()`.
+pub(super) static BYTES: Namespace = Namespace {
+	name: "bytes",
+	members: phf::phf_map! {
+		"len" => Member::function("bytes.len", len),
+		"slice" => Member::function("bytes.slice", slice),
+		"decode" => Member::function("bytes.decode", decode),
+	},
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Encoding {
+	Utf8,
+	Ascii,
+	Latin1,
+}
+
+impl Encoding {
+	fn from_name(name: &str) -> Option<Self> {
+		match name.to_ascii_lowercase().as_str() {
+			"utf-8" | "utf8" => Some(Encoding::Utf8),
+			"ascii" => Some(Encoding::Ascii),
+			"latin-1" | "latin1" | "iso-8859-1" => Some(Encoding::Latin1),
+			_ => None,
+		}
+	}
+
+	fn encode(self, st: &str) -> Option<Vec<u8>> {
+		match self {
+			Encoding::Utf8 => Some(st.as_bytes().to_vec()),
+			Encoding::Ascii => st.is_ascii().then(|| st.as_bytes().to_vec()),
+			Encoding::Latin1 => st.chars().map(|ch| u8::try_from(ch).ok()).collect(),
+		}
+	}
+
+	fn decode(self, bytes: &[u8]) -> Option<String> {
+		match self {
+			Encoding::Utf8 => String::from_utf8(bytes.to_vec()).ok(),
+			Encoding::Ascii => bytes
+				.is_ascii()
+				.then(|| bytes.iter().copied().map(char::from).collect()),
+			Encoding::Latin1 => Some(bytes.iter().copied().map(char::from).collect()),
+		}
+	}
+}
+
+fn invalid(name: &str, arg: &RuntimeValue) -> RuntimeError {
+	RuntimeError::InvalidArgument(Box::from(name), arg.to_owned())
+}
+
+fn expect_bytes<'a>(name: &str, arg: &'a RuntimeValue) -> Result<&'a [u8], RuntimeError> {
+	match arg {
+		RuntimeValue::Bytes(bytes) => Ok(bytes),
+		other => Err(invalid(name, other)),
+	}
+}
+
+/// Splits the arguments of `encode` and `decode` into the value and the
+/// encoding, which is UTF-8 unless named.
+fn encoding_args<'a>(
+	name: &str,
+	args: &'a [RuntimeValue],
+) -> Result<(&'a RuntimeValue, Encoding), RuntimeError> {
+	match args {
+		[value] => Ok((value, Encoding::Utf8)),
+		[value, encoding] => Encoding::from_name(expect_string(name, encoding)?)
+			.map(|encoding| (value, encoding))
+			.ok_or_else(|| invalid(name, encoding)),
+		_ => Err(RuntimeError::WrongArgumentCount(
+			Box::from(name),
+			1,
+			args.len(),
+		)),
+	}
+}
+
+fn len(args: &[RuntimeValue]) -> BuiltinResult {
+	let [bytes] = expect_args("bytes.len", args)?;
+	let len = expect_bytes("bytes.len", bytes)?.len();
+	Ok(RuntimeValue::Number(Numeric::Int(len as i128)))
+}
+
+/// `b.slice(start, end)` copies the bytes from `start` up to but not
+/// including `end`.
+fn slice(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "bytes.slice";
+	let [bytes, start_arg, end_arg] = expect_args(name, args)?;
+	let bytes = expect_bytes(name, bytes)?;
+	let bound = |arg: &RuntimeValue| match arg {
+		RuntimeValue::Number(Numeric::Int(idx)) => {
+			usize::try_from(*idx).ok().filter(|&idx| idx <= bytes.len())
+		}
+		_ => None,
+	};
+	let start = bound(start_arg).ok_or_else(|| invalid(name, start_arg))?;
+	let end = bound(end_arg)
+		.filter(|&end| end >= start)
+		.ok_or_else(|| invalid(name, end_arg))?;
+	Ok(RuntimeValue::Bytes(bytes[start..end].to_vec()))
+}
+
+/// `b.decode()` reads UTF-8, `b.decode(encoding)` also takes `"ascii"` and
+/// `"latin-1"`. Fails on bytes that aren't valid in the encoding.
+fn decode(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "bytes.decode";
+	let (bytes, encoding) = encoding_args(name, args)?;
+	encoding
+		.decode(expect_bytes(name, bytes)?)
+		.map(RuntimeValue::String)
+		.ok_or_else(|| invalid(name, bytes))
+}
+
+/// `s.encode()` gives the UTF-8 bytes of a string, `s.encode(encoding)` also
+/// takes `"ascii"` and `"latin-1"`. Fails on chars the encoding can't hold.
+pub(super) fn encode(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.encode";
+	let (st, encoding) = encoding_args(name, args)?;
+	encoding
+		.encode(expect_string(name, st)?)
+		.map(RuntimeValue::Bytes)
+		.ok_or_else(|| invalid(name, st))
+}
@@ -0,0 +1,69 @@
+use crate::{
+	numeric::Numeric,
+	runtime::{error::RuntimeError, value::RuntimeValue},
+};
+
+use super::{expect_args, BuiltinResult, Member, Namespace};
+
+/// Char functions, also callable as methods: `c.code()` is `char.code(c)`.
+pub(super) static CHAR: Namespace = Namespace {
+	name: "char",
+	members: phf::phf_map! {
+		"code" => Member::function("char.code", code),
+		"from_code" => Member::function("char.from_code", from_code),
+		"is_alphabetic" => Member::function("char.is_alphabetic", is_alphabetic),
+		"is_numeric" => Member::function("char.is_numeric", is_numeric),
+		"is_whitespace" => Member::function("char.is_whitespace", is_whitespace),
+	},
+};
+
+fn expect_char(name: &str, arg: &RuntimeValue) -> Result<char, RuntimeError> {
+	match arg {
+		RuntimeValue::Char(ch) => Ok(*ch),
+		other => Err(RuntimeError::InvalidArgument(
+			Box::from(name),
+			other.to_owned(),
+		)),
+	}
+}
+
+fn predicate(name: &str, args: &[RuntimeValue], op: fn(char) -> bool) -> BuiltinResult {
+	let [ch] = expect_args(name, args)?;
+	Ok(RuntimeValue::Number(Numeric::Int(
+		op(expect_char(name, ch)?) as i128,
+	)))
+}
+
+/// The Unicode code point of the char.
+fn code(args: &[RuntimeValue]) -> BuiltinResult {
+	let [ch] = expect_args("char.code", args)?;
+	Ok(RuntimeValue::Number(Numeric::Int(
+		expect_char("char.code", ch)? as i128,
+	)))
+}
+
+/// Fails for surrogates and numbers past `0x10FFFF`.
+fn from_code(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "char.from_code";
+	let [arg] = expect_args(name, args)?;
+	match arg {
+		RuntimeValue::Number(Numeric::Int(code)) => {
+			u32::try_from(*code).ok().and_then(char::from_u32)
+		}
+		_ => None,
+	}
+	.map(RuntimeValue::Char)
+	.ok_or_else(|| RuntimeError::InvalidArgument(Box::from(name), arg.to_owned()))
+}
+
+fn is_alphabetic(args: &[RuntimeValue]) -> BuiltinResult {
+	predicate("char.is_alphabetic", args, char::is_alphabetic)
+}
+
+fn is_numeric(args: &[RuntimeValue]) -> BuiltinResult {
+	predicate("char.is_numeric", args, char::is_numeric)
+}
+
+fn is_whitespace(args: &[RuntimeValue]) -> BuiltinResult {
+	predicate("char.is_whitespace", args, char::is_whitespace)
+}
@@ -0,0 +1,171 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use crate::{
+	numeric::Numeric,
+	runtime::{error::RuntimeError, value::RuntimeValue},
+};
+
+use super::{expect_string, BuiltinResult};
+
+/// How strings are ordered. Chars are ordered like one-char strings.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum CompareMode {
+	/// By Unicode code point, like the `<` operator.
+	CodePoint,
+	/// By code point after lowercasing.
+	CaseInsensitive,
+	/// Runs of ASCII digits are compared by their value, so `"file2"` comes
+	/// before `"file10"`.
+	Natural,
+}
+
+impl CompareMode {
+	const NAMES: &'static [&'static str] = &["code_point", "case_insensitive", "natural"];
+}
+
+impl FromStr for CompareMode {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"code_point" | "codepoint" => Ok(CompareMode::CodePoint),
+			"case_insensitive" => Ok(CompareMode::CaseInsensitive),
+			"natural" => Ok(CompareMode::Natural),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Splits off the leading run of chars that are all digits or all not.
+fn split_chunk(st: &str) -> (&str, &str) {
+	let digits = st.starts_with(|ch: char| ch.is_ascii_digit());
+	st.split_at(
+		st.find(|ch: char| ch.is_ascii_digit() != digits)
+			.unwrap_or(st.len()),
+	)
+}
+
+fn natural_cmp(mut left: &str, mut right: &str) -> Ordering {
+	while !left.is_empty() && !right.is_empty() {
+		let ((l, l_rest), (r, r_rest)) = (split_chunk(left), split_chunk(right));
+		let both_digits = [l, r]
+			.iter()
+			.all(|chunk| chunk.starts_with(|ch: char| ch.is_ascii_digit()));
+		let ordering = if both_digits {
+			let (l, r) = (l.trim_start_matches('0'), r.trim_start_matches('0'));
+			l.len().cmp(&r.len()).then_with(|| l.cmp(r))
+		} else {
+			l.cmp(r)
+		};
+		if ordering.is_ne() {
+			return ordering;
+		}
+		(left, right) = (l_rest, r_rest);
+	}
+	left.len().cmp(&right.len())
+}
+
+impl CompareMode {
+	/// Ties in natural order, such as `"a01"` and `"a1"`, are broken by code
+	/// point.
+	pub(super) fn compare(self, left: &str, right: &str) -> Ordering {
+		match self {
+			CompareMode::CodePoint => left.cmp(right),
+			CompareMode::CaseInsensitive => left
+				.chars()
+				.flat_map(char::to_lowercase)
+				.cmp(right.chars().flat_map(char::to_lowercase)),
+			CompareMode::Natural => natural_cmp(left, right).then_with(|| left.cmp(right)),
+		}
+	}
+}
+
+/// Orders two values for sorting. Numbers are ordered with
+/// `Numeric::total_cmp`, strings and chars by `mode`. `None` for values of
+/// different or unordered types.
+pub(super) fn compare_values(
+	left: &RuntimeValue,
+	right: &RuntimeValue,
+	mode: CompareMode,
+) -> Option<Ordering> {
+	match (left, right) {
+		(RuntimeValue::Number(l), RuntimeValue::Number(r)) => Some(l.total_cmp(r)),
+		(
+			RuntimeValue::String(_) | RuntimeValue::Char(_),
+			RuntimeValue::String(_) | RuntimeValue::Char(_),
+		) if left.discriminant() == right.discriminant() => {
+			Some(mode.compare(&left.to_text(), &right.to_text()))
+		}
+		_ => None,
+	}
+}
+
+/// `compare(a, b)` or `compare(a, b, mode)` returns -1, 0 or 1. The mode is
+/// one of `"code_point"`, `"case_insensitive"` or `"natural"`.
+pub(super) fn compare(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "compare";
+	let (left, right, mode) = match args {
+		[left, right] => (left, right, CompareMode::CodePoint),
+		[left, right, mode_arg] => {
+			let mode = expect_string(name, mode_arg)?;
+			let mode = mode.parse().map_err(|_| {
+				RuntimeError::UnknownMode(Box::from(name), Box::from(mode), CompareMode::NAMES)
+			})?;
+			(left, right, mode)
+		}
+		_ => {
+			return Err(RuntimeError::WrongArgumentCount(
+				Box::from(name),
+				2,
+				args.len(),
+			))
+		}
+	};
+	let ordering = compare_values(left, right, mode)
+		.ok_or_else(|| RuntimeError::InvalidArgument(Box::from(name), right.to_owned()))?;
+	Ok(RuntimeValue::Number(Numeric::Int(ordering as i128)))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cmp::Ordering::*;
+
+	use super::CompareMode::{self, *};
+
+	fn mode(name: &str) -> CompareMode {
+		name.parse().unwrap()
+	}
+
+	#[test]
+	fn code_points() {
+		assert_eq!(CodePoint.compare("a", "b"), Less);
+		assert_eq!(CodePoint.compare("B", "a"), Less);
+		assert_eq!(CodePoint.compare("file10", "file9"), Less);
+		assert_eq!(CodePoint.compare("é", "z"), Greater);
+	}
+
+	#[test]
+	fn case_insensitive() {
+		assert_eq!(CaseInsensitive.compare("B", "a"), Greater);
+		assert_eq!(CaseInsensitive.compare("ÉTÉ", "été"), Equal);
+		assert_eq!(CaseInsensitive.compare("ab", "AbC"), Less);
+	}
+
+	#[test]
+	fn natural() {
+		assert_eq!(Natural.compare("file10", "file9"), Greater);
+		assert_eq!(Natural.compare("file2", "file10"), Less);
+		assert_eq!(Natural.compare("a01", "a1"), Less);
+		assert_eq!(Natural.compare("a1b", "a1"), Greater);
+		assert_eq!(Natural.compare("x", "1"), Greater);
+	}
+
+	#[test]
+	fn mode_names() {
+		assert!(matches!(mode("code_point"), CodePoint));
+		assert!(matches!(mode("codepoint"), CodePoint));
+		assert!(matches!(mode("case_insensitive"), CaseInsensitive));
+		assert!(matches!(mode("natural"), Natural));
+		assert!("lexical".parse::<CompareMode>().is_err());
+	}
+}
@@ -0,0 +1,70 @@
+use crate::{
+	numeric::{
+		decimal::{Decimal, RoundingMode, MAX_PLACES},
+		error::NumericError,
+		Numeric,
+	},
+	runtime::{error::RuntimeError, value::RuntimeValue},
+};
+
+use super::{expect_args, expect_number, BuiltinResult, Member, Namespace};
+
+pub(super) static DECIMAL: Namespace = Namespace {
+	name: "decimal",
+	members: phf::phf_map! {
+		"div" => Member::function("decimal.div", div),
+		"round" => Member::function("decimal.round", round),
+	},
+};
+
+fn invalid(name: &str, arg: &RuntimeValue) -> RuntimeError {
+	RuntimeError::InvalidArgument(Box::from(name), arg.to_owned())
+}
+
+fn expect_decimal(name: &str, arg: &RuntimeValue) -> Result<Decimal, RuntimeError> {
+	expect_number(name, arg)?
+		.to_decimal()
+		.ok_or_else(|| invalid(name, arg))
+}
+
+fn expect_places(name: &str, arg: &RuntimeValue) -> Result<u32, RuntimeError> {
+	match arg {
+		RuntimeValue::Number(Numeric::Int(i)) => u32::try_from(*i)
+			.ok()
+			.filter(|&places| places <= MAX_PLACES),
+		_ => None,
+	}
+	.ok_or_else(|| invalid(name, arg))
+}
+
+fn expect_mode(name: &str, arg: &RuntimeValue) -> Result<RoundingMode, RuntimeError> {
+	match arg {
+		RuntimeValue::String(mode) => mode.parse().ok(),
+		_ => None,
+	}
+	.ok_or_else(|| invalid(name, arg))
+}
+
+/// `decimal.div(x, y, places, mode)` divides to exactly `places` fractional
+/// digits, rounding with one of `up`, `down`, `ceiling`, `floor`, `half_up`,
+/// `half_down` or `half_even`.
+fn div(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "decimal.div";
+	let [x, y, places, mode] = expect_args(name, args)?;
+	let quotient = expect_decimal(name, x)?
+		.div(
+			&expect_decimal(name, y)?,
+			expect_places(name, places)?,
+			expect_mode(name, mode)?,
+		)
+		.ok_or(NumericError::DivisionByZero)?;
+	Ok(RuntimeValue::Number(Numeric::Decimal(quotient)))
+}
+
+fn round(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "decimal.round";
+	let [x, places, mode] = expect_args(name, args)?;
+	let rounded =
+		expect_decimal(name, x)?.round(expect_places(name, places)?, expect_mode(name, mode)?);
+	Ok(RuntimeValue::Number(Numeric::Decimal(rounded)))
+}
@@ -0,0 +1,345 @@
+use num_bigint::BigInt;
+
+use crate::{
+	environment::Env,
+	numeric::{decimal::MAX_PLACES, Numeric},
+	runtime::{error::RuntimeError, value::RuntimeValue},
+};
+
+use super::{expect_args, expect_number, expect_string, BuiltinResult};
+
+#[derive(Debug, Clone, Copy)]
+enum Align {
+	Left,
+	Right,
+	Center,
+	AfterSign,
+}
+
+impl Align {
+	fn from_char(ch: char) -> Option<Self> {
+		match ch {
+			'<' => Some(Align::Left),
+			'>' => Some(Align::Right),
+			'^' => Some(Align::Center),
+			'=' => Some(Align::AfterSign),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NumberKind {
+	General,
+	Fixed,
+	Exponent,
+	Radix(u32, bool),
+}
+
+/// A format spec in the form `[[fill]align][+][#][0][width][,|_][.precision][type]`,
+/// where `align` is one of `<`, `>`, `^` or `=` and `type` one of `f`, `e`,
+/// `x`, `X`, `o` or `b`. Neither the width nor the precision may exceed
+/// `MAX_PLACES`.
+#[derive(Debug)]
+pub(super) struct NumberSpec {
+	fill: char,
+	align: Option<Align>,
+	plus: bool,
+	alternate: bool,
+	width: usize,
+	separator: Option<char>,
+	precision: Option<u32>,
+	kind: NumberKind,
+}
+
+fn split_digits(src: &str) -> (&str, &str) {
+	src.split_at(
+		src.find(|ch: char| !ch.is_ascii_digit())
+			.unwrap_or(src.len()),
+	)
+}
+
+/// Inserts `separator` between groups of digits in the leading run of digits
+/// of `src`. Decimal digits are grouped by three, hex, octal and binary ones by
+/// four.
+fn group(src: &str, separator: char, radix: bool) -> String {
+	let (size, is_digit): (usize, fn(&char) -> bool) = if radix {
+		(4, char::is_ascii_alphanumeric)
+	} else {
+		(3, char::is_ascii_digit)
+	};
+	let end = src.find(|ch| !is_digit(&ch)).unwrap_or(src.len());
+	let (digits, rest) = src.split_at(end);
+	let mut grouped = String::new();
+	for (idx, digit) in digits.chars().enumerate() {
+		if idx > 0 && (digits.len() - idx) % size == 0 {
+			grouped.push(separator);
+		}
+		grouped.push(digit);
+	}
+	grouped + rest
+}
+
+impl NumberSpec {
+	pub(super) fn parse(spec: &str) -> Option<Self> {
+		let mut result = Self {
+			fill: ' ',
+			align: None,
+			plus: false,
+			alternate: false,
+			width: 0,
+			separator: None,
+			precision: None,
+			kind: NumberKind::General,
+		};
+
+		let mut rest = spec;
+		let mut chars = rest.chars();
+		if let (Some(fill), Some(align)) = (chars.next(), chars.next().and_then(Align::from_char)) {
+			(result.fill, result.align) = (fill, Some(align));
+			rest = &rest[fill.len_utf8() + 1..];
+		} else if let Some(align) = rest.chars().next().and_then(Align::from_char) {
+			result.align = Some(align);
+			rest = &rest[1..];
+		}
+
+		if let Some(stripped) = rest.strip_prefix('+') {
+			result.plus = true;
+			rest = stripped;
+		}
+		if let Some(stripped) = rest.strip_prefix('#') {
+			result.alternate = true;
+			rest = stripped;
+		}
+		if let Some(stripped) = rest.strip_prefix('0') {
+			(result.fill, result.align) = ('0', Some(Align::AfterSign));
+			rest = stripped;
+		}
+
+		let (width, stripped) = split_digits(rest);
+		if !width.is_empty() {
+			result.width = width
+				.parse()
+				.ok()
+				.filter(|&width| width <= MAX_PLACES as usize)?;
+		}
+		rest = stripped;
+
+		if let Some(separator) = rest.chars().next().filter(|ch| matches!(ch, ',' | '_')) {
+			result.separator = Some(separator);
+			rest = &rest[1..];
+		}
+		if let Some(stripped) = rest.strip_prefix('.') {
+			let (precision, stripped) = split_digits(stripped);
+			result.precision = Some(
+				precision
+					.parse()
+					.ok()
+					.filter(|&precision| precision <= MAX_PLACES)?,
+			);
+			rest = stripped;
+		}
+
+		result.kind = match rest {
+			"" => NumberKind::General,
+			"f" => NumberKind::Fixed,
+			"e" => NumberKind::Exponent,
+			"x" => NumberKind::Radix(16, false),
+			"X" => NumberKind::Radix(16, true),
+			"o" => NumberKind::Radix(8, false),
+			"b" => NumberKind::Radix(2, false),
+			_ => return None,
+		};
+		Some(result)
+	}
+
+	/// The number without padding, `None` if the type needs an integer.
+	fn render(&self, number: &Numeric) -> Option<String> {
+		match (self.kind, self.precision) {
+			(NumberKind::General, None) => Some(number.to_text()),
+			(NumberKind::General | NumberKind::Fixed, precision) => {
+				Some(number.to_fixed(precision.unwrap_or(6)))
+			}
+			(NumberKind::Exponent, precision) => match number.to_f64() {
+				fl if fl.is_finite() => Some(format!("{fl:.*e}", precision.unwrap_or(6) as usize)),
+				fl => Some(Numeric::Float(fl).to_text()),
+			},
+			(NumberKind::Radix(radix, uppercase), _) => {
+				let digits = match number {
+					Numeric::Int(i) => BigInt::from(*i).to_str_radix(radix),
+					Numeric::Big(b) => b.to_str_radix(radix),
+					_ => return None,
+				};
+				Some(if uppercase {
+					digits.to_uppercase()
+				} else {
+					digits
+				})
+			}
+		}
+	}
+
+	pub(super) fn format(&self, number: &Numeric) -> Option<String> {
+		let (magnitude, unit) = match number {
+			Numeric::Quantity(magnitude, dimension) => (&**magnitude, format!(" {dimension}")),
+			other => (other, String::new()),
+		};
+
+		let rendered = self.render(magnitude)?;
+		let (negative, digits) = match rendered.strip_prefix('-') {
+			Some(digits) => (true, digits),
+			None => (false, rendered.as_str()),
+		};
+
+		let mut prefix = String::from(match (negative, self.plus) {
+			(true, _) => "-",
+			(false, true) => "+",
+			(false, false) => "",
+		});
+		if let (true, NumberKind::Radix(radix, _)) = (self.alternate, self.kind) {
+			prefix.push_str(match radix {
+				16 => "0x",
+				8 => "0o",
+				_ => "0b",
+			});
+		}
+
+		let body = match self.separator {
+			Some(separator) => group(
+				digits,
+				separator,
+				matches!(self.kind, NumberKind::Radix(..)),
+			),
+			None => digits.to_owned(),
+		} + &unit;
+
+		Some(self.pad(prefix, &body, Align::Right))
+	}
+
+	/// Formats text, which only takes a fill, an alignment other than `=`, a
+	/// width and a precision that cuts the text to as many chars.
+	fn format_text(&self, text: &str) -> Option<String> {
+		let numeric = self.plus
+			|| self.alternate
+			|| self.separator.is_some()
+			|| !matches!(self.kind, NumberKind::General)
+			|| matches!(self.align, Some(Align::AfterSign));
+		if numeric {
+			return None;
+		}
+		let text: String = match self.precision {
+			Some(precision) => text.chars().take(precision as usize).collect(),
+			None => text.to_owned(),
+		};
+		Some(self.pad(String::new(), &text, Align::Left))
+	}
+
+	fn pad(&self, prefix: String, body: &str, default: Align) -> String {
+		let padding = self
+			.width
+			.saturating_sub(prefix.chars().count() + body.chars().count());
+		let fill = |count: usize| self.fill.to_string().repeat(count);
+		match self.align.unwrap_or(default) {
+			Align::Left => prefix + body + &fill(padding),
+			Align::Right => fill(padding) + &prefix + body,
+			Align::Center => fill(padding / 2) + &prefix + body + &fill(padding - padding / 2),
+			Align::AfterSign => prefix + &fill(padding) + body,
+		}
+	}
+}
+
+/// `format_number(x, spec)` formats a number according to a `NumberSpec`.
+pub(super) fn format_number(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "format_number";
+	let [x, spec_arg] = expect_args(name, args)?;
+	let number = expect_number(name, x)?;
+	let spec = match spec_arg {
+		RuntimeValue::String(spec) => NumberSpec::parse(spec),
+		_ => None,
+	}
+	.ok_or_else(|| RuntimeError::InvalidArgument(Box::from(name), spec_arg.to_owned()))?;
+
+	spec.format(number)
+		.map(RuntimeValue::String)
+		.ok_or_else(|| RuntimeError::InvalidArgument(Box::from(name), x.to_owned()))
+}
+
+/// Formats a value for a placeholder spec, which is a `NumberSpec` optionally
+/// followed by `?` to show strings and chars quoted as in source code.
+fn format_value(value: &RuntimeValue, spec: &str) -> Option<String> {
+	let (spec, quoted) = match spec.strip_suffix('?') {
+		Some(spec) => (spec, true),
+		None => (spec, false),
+	};
+	let spec = NumberSpec::parse(spec)?;
+	match value {
+		RuntimeValue::Number(number) => spec.format(number),
+		other if quoted => spec.format_text(&other.to_string()),
+		other => spec.format_text(&other.to_text()),
+	}
+}
+
+/// `format(template, args..)` replaces placeholders in the template, which are
+/// `{}` for the next argument, `{2}` for an argument by position or `{name}`
+/// for a variable, each optionally followed by `:spec`. `{{` and `}}` stand
+/// for literal braces. Plain strings work as templates since the lexer leaves
+/// `{}`, `{0}` and placeholders with a spec such as `{:>8}` or `{x:.2}`
+/// alone. A plain `{name}` is interpolated before `format` sees it, and the
+/// lexer already turns `{{` into `{`, so literal braces in the output need a
+/// raw string.
+pub(super) fn format(env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "format";
+	let Some((template, values)) = args.split_first() else {
+		return Err(RuntimeError::TooFewArguments(Box::from(name), 1, 0));
+	};
+	let template = expect_string(name, template)?;
+
+	let mut result = String::new();
+	let mut next = 0;
+	let mut chars = template.char_indices().peekable();
+	while let Some((start, ch)) = chars.next() {
+		let end = match ch {
+			'{' | '}' if chars.next_if(|(_, next)| *next == ch).is_some() => {
+				result.push(ch);
+				continue;
+			}
+			'{' => chars.find(|(_, ch)| *ch == '}').map(|(end, _)| end + 1),
+			'}' => Some(start + 1),
+			other => {
+				result.push(other);
+				continue;
+			}
+		};
+		let placeholder = &template[start..end.unwrap_or(template.len())];
+		let invalid = || {
+			RuntimeError::InvalidPlaceholder(
+				Box::from(placeholder),
+				template[..start].chars().count() + 1,
+			)
+		};
+		let Some(inner) = placeholder
+			.strip_prefix('{')
+			.and_then(|rest| rest.strip_suffix('}'))
+		else {
+			return Err(invalid());
+		};
+
+		let (argument, spec) = inner.split_once(':').unwrap_or((inner, ""));
+		let value = if argument.is_empty() {
+			next += 1;
+			values.get(next - 1).cloned()
+		} else if argument.starts_with(|ch: char| ch.is_ascii_digit()) {
+			argument
+				.parse::<usize>()
+				.ok()
+				.and_then(|idx| values.get(idx).cloned())
+		} else if argument.chars().all(|ch| ch.is_alphanumeric() || ch == '_') {
+			Some(env.evaluate(argument)?.inner().to_owned())
+		} else {
+			None
+		};
+		let formatted = value.and_then(|value| format_value(&value, spec));
+		result.push_str(&formatted.ok_or_else(invalid)?);
+	}
+	Ok(RuntimeValue::String(result))
+}
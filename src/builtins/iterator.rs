@@ -0,0 +1,205 @@
+use std::ops::Add;
+
+use crate::{
+	environment::Env,
+	numeric::Numeric,
+	runtime::{error::RuntimeError, iterator::IteratorValue, value::RuntimeValue},
+};
+
+use super::{expect_args, BuiltinResult, Member, Namespace};
+
+/// Iterator functions, also callable as methods on anything iterable:
+/// `(0..10).map(f)` is `iterator.map(0..10, f)`. Adapters are lazy, only the
+/// consumers from `collect` on take items, and `next` takes one.
+pub(super) static ITERATOR: Namespace = Namespace {
+	name: "iterator",
+	members: phf::phf_map! {
+		"iter" => Member::function("iterator.iter", iter),
+		"next" => Member::scoped_function("iterator.next", next),
+		"map" => Member::function("iterator.map", map),
+		"filter" => Member::function("iterator.filter", filter),
+		"take" => Member::function("iterator.take", take),
+		"skip" => Member::function("iterator.skip", skip),
+		"zip" => Member::function("iterator.zip", zip),
+		"enumerate" => Member::function("iterator.enumerate", enumerate),
+		"chain" => Member::function("iterator.chain", chain),
+		"flat_map" => Member::function("iterator.flat_map", flat_map),
+		"collect" => Member::scoped_function("iterator.collect", collect),
+		"sum" => Member::scoped_function("iterator.sum", sum),
+		"count" => Member::scoped_function("iterator.count", count),
+		"any" => Member::scoped_function("iterator.any", any),
+		"all" => Member::scoped_function("iterator.all", all),
+		"fold" => Member::scoped_function("iterator.fold", fold),
+	},
+};
+
+fn expect_callable(name: &str, arg: &RuntimeValue) -> Result<RuntimeValue, RuntimeError> {
+	match arg {
+		RuntimeValue::Function(_) | RuntimeValue::Constructor(..) => Ok(arg.to_owned()),
+		other => Err(RuntimeError::InvalidArgument(
+			Box::from(name),
+			other.to_owned(),
+		)),
+	}
+}
+
+fn expect_count(name: &str, arg: &RuntimeValue) -> Result<usize, RuntimeError> {
+	match arg {
+		RuntimeValue::Number(Numeric::Int(count)) => usize::try_from(*count).ok(),
+		_ => None,
+	}
+	.ok_or_else(|| RuntimeError::InvalidArgument(Box::from(name), arg.to_owned()))
+}
+
+fn boolean(value: bool) -> RuntimeValue {
+	RuntimeValue::Number(Numeric::Int(value as i128))
+}
+
+fn iter(args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable] = expect_args("iterator.iter", args)?;
+	Ok(RuntimeValue::Iterator(iterable.to_owned().iterate()?))
+}
+
+/// The next item of an iterator, `_` once it is exhausted.
+fn next(env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "iterator.next";
+	let [iterator] = expect_args(name, args)?;
+	let RuntimeValue::Iterator(iterator) = iterator else {
+		return Err(RuntimeError::InvalidArgument(
+			Box::from(name),
+			iterator.to_owned(),
+		));
+	};
+	Ok(iterator.next(env)?.unwrap_or(RuntimeValue::Unit))
+}
+
+fn adapter(
+	name: &str,
+	args: &[RuntimeValue],
+	adapt: fn(IteratorValue, RuntimeValue) -> IteratorValue,
+) -> BuiltinResult {
+	let [iterable, func] = expect_args(name, args)?;
+	let func = expect_callable(name, func)?;
+	let iterator = iterable.to_owned().iterate()?;
+	Ok(RuntimeValue::Iterator(adapt(iterator, func)))
+}
+
+fn map(args: &[RuntimeValue]) -> BuiltinResult {
+	adapter("iterator.map", args, IteratorValue::map)
+}
+
+fn filter(args: &[RuntimeValue]) -> BuiltinResult {
+	adapter("iterator.filter", args, IteratorValue::filter)
+}
+
+fn flat_map(args: &[RuntimeValue]) -> BuiltinResult {
+	adapter("iterator.flat_map", args, IteratorValue::flat_map)
+}
+
+fn take(args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable, count] = expect_args("iterator.take", args)?;
+	let count = expect_count("iterator.take", count)?;
+	let iterator = iterable.to_owned().iterate()?;
+	Ok(RuntimeValue::Iterator(iterator.take(count)))
+}
+
+fn skip(args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable, count] = expect_args("iterator.skip", args)?;
+	let count = expect_count("iterator.skip", count)?;
+	let iterator = iterable.to_owned().iterate()?;
+	Ok(RuntimeValue::Iterator(iterator.skip(count)))
+}
+
+/// Pairs up the items of two iterables, stopping at the end of the shorter.
+fn zip(args: &[RuntimeValue]) -> BuiltinResult {
+	let [left, right] = expect_args("iterator.zip", args)?;
+	let (left, right) = (left.to_owned().iterate()?, right.to_owned().iterate()?);
+	Ok(RuntimeValue::Iterator(left.zip(right)))
+}
+
+/// Pairs every item with its index, as in `(0, item)`.
+fn enumerate(args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable] = expect_args("iterator.enumerate", args)?;
+	Ok(RuntimeValue::Iterator(
+		iterable.to_owned().iterate()?.enumerate(),
+	))
+}
+
+fn chain(args: &[RuntimeValue]) -> BuiltinResult {
+	let [first, second] = expect_args("iterator.chain", args)?;
+	let (first, second) = (first.to_owned().iterate()?, second.to_owned().iterate()?);
+	Ok(RuntimeValue::Iterator(first.chain(second)))
+}
+
+fn collect(env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable] = expect_args("iterator.collect", args)?;
+	let iterator = iterable.to_owned().iterate()?;
+	let mut items = Vec::new();
+	while let Some(item) = iterator.next(env)? {
+		items.push(item);
+	}
+	Ok(RuntimeValue::List(items))
+}
+
+/// Adds up the items with `+`, starting from the first so quantities and
+/// strings can be summed too. The sum of nothing is 0.
+fn sum(env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable] = expect_args("iterator.sum", args)?;
+	let iterator = iterable.to_owned().iterate()?;
+	let Some(mut total) = iterator.next(env)? else {
+		return Ok(RuntimeValue::Number(Numeric::Int(0)));
+	};
+	while let Some(item) = iterator.next(env)? {
+		total = total.add(&item)?;
+	}
+	Ok(total)
+}
+
+fn count(env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable] = expect_args("iterator.count", args)?;
+	let iterator = iterable.to_owned().iterate()?;
+	let mut count = 0;
+	while iterator.next(env)?.is_some() {
+		count += 1;
+	}
+	Ok(RuntimeValue::Number(Numeric::Int(count)))
+}
+
+/// Whether `func` holds for some item, stopping at the first one it does.
+fn any(env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable, func] = expect_args("iterator.any", args)?;
+	let func = expect_callable("iterator.any", func)?;
+	let iterator = iterable.to_owned().iterate()?;
+	while let Some(item) = iterator.next(env)? {
+		if func.call(env, vec![item])?.is_truthy()? {
+			return Ok(boolean(true));
+		}
+	}
+	Ok(boolean(false))
+}
+
+/// Whether `func` holds for every item, stopping at the first one it doesn't.
+fn all(env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable, func] = expect_args("iterator.all", args)?;
+	let func = expect_callable("iterator.all", func)?;
+	let iterator = iterable.to_owned().iterate()?;
+	while let Some(item) = iterator.next(env)? {
+		if !func.call(env, vec![item])?.is_truthy()? {
+			return Ok(boolean(false));
+		}
+	}
+	Ok(boolean(true))
+}
+
+/// Combines the items from left to right as `func(acc, item)`, starting with
+/// `init`.
+fn fold(env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+	let [iterable, init, func] = expect_args("iterator.fold", args)?;
+	let func = expect_callable("iterator.fold", func)?;
+	let iterator = iterable.to_owned().iterate()?;
+	let mut acc = init.to_owned();
+	while let Some(item) = iterator.next(env)? {
+		acc = func.call(env, vec![acc, item])?;
+	}
+	Ok(acc)
+}
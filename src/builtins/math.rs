@@ -0,0 +1,269 @@
+use std::{cmp::Ordering, f64::consts, ops::Rem};
+
+use num_traits::Pow;
+
+use crate::{
+	numeric::Numeric,
+	runtime::{error::RuntimeError, value::RuntimeValue},
+};
+
+use super::{expect_args, expect_dimensionless, expect_number, BuiltinResult, Member, Namespace};
+
+pub(super) static MATH: Namespace = Namespace {
+	name: "math",
+	members: phf::phf_map! {
+		"sqrt" => Member::function("math.sqrt", sqrt),
+		"pow" => Member::function("math.pow", pow),
+		"abs" => Member::function("math.abs", abs),
+		"floor" => Member::function("math.floor", floor),
+		"ceil" => Member::function("math.ceil", ceil),
+		"round" => Member::function("math.round", round),
+		"trunc" => Member::function("math.trunc", trunc),
+		"min" => Member::function("math.min", min),
+		"max" => Member::function("math.max", max),
+		"clamp" => Member::function("math.clamp", clamp),
+		"sin" => Member::function("math.sin", sin),
+		"cos" => Member::function("math.cos", cos),
+		"tan" => Member::function("math.tan", tan),
+		"atan2" => Member::function("math.atan2", atan2),
+		"ln" => Member::function("math.ln", ln),
+		"log10" => Member::function("math.log10", log10),
+		"exp" => Member::function("math.exp", exp),
+		"gcd" => Member::function("math.gcd", gcd),
+		"lcm" => Member::function("math.lcm", lcm),
+		"pi" => Member::Constant(Numeric::Float(consts::PI)),
+		"e" => Member::Constant(Numeric::Float(consts::E)),
+		"inf" => Member::Constant(Numeric::Float(f64::INFINITY)),
+		"nan" => Member::Constant(Numeric::Float(f64::NAN)),
+	},
+};
+
+fn unary(name: &str, args: &[RuntimeValue], op: fn(Numeric) -> Numeric) -> BuiltinResult {
+	let [x] = expect_args(name, args)?;
+	Ok(RuntimeValue::Number(op(expect_number(name, x)?.to_owned())))
+}
+
+fn float_unary(name: &str, args: &[RuntimeValue], op: fn(f64) -> f64) -> BuiltinResult {
+	let [x] = expect_args(name, args)?;
+	let x = expect_dimensionless(name, x)?.to_f64();
+	Ok(RuntimeValue::Number(Numeric::Float(op(x))))
+}
+
+/// Truncating remainder, which takes the sign of the dividend, unlike `%`.
+pub(super) fn rem(args: &[RuntimeValue]) -> BuiltinResult {
+	let [x, y] = expect_args("rem", args)?;
+	let (x, y) = (expect_number("rem", x)?, expect_number("rem", y)?);
+	Ok(RuntimeValue::Number(x.to_owned().rem(y.to_owned())?))
+}
+
+fn sqrt(args: &[RuntimeValue]) -> BuiltinResult {
+	let [x] = expect_args("math.sqrt", args)?;
+	Ok(RuntimeValue::Number(
+		expect_number("math.sqrt", x)?.to_owned().sqrt()?,
+	))
+}
+
+fn pow(args: &[RuntimeValue]) -> BuiltinResult {
+	let [x, y] = expect_args("math.pow", args)?;
+	let (x, y) = (expect_number("math.pow", x)?, expect_number("math.pow", y)?);
+	Ok(RuntimeValue::Number(x.to_owned().pow(y.to_owned())?))
+}
+
+fn abs(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("math.abs", args, Numeric::abs)
+}
+
+fn floor(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("math.floor", args, Numeric::floor)
+}
+
+fn ceil(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("math.ceil", args, Numeric::ceil)
+}
+
+fn round(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("math.round", args, Numeric::round)
+}
+
+fn trunc(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("math.trunc", args, Numeric::trunc)
+}
+
+/// Picks the argument that compares as `wanted` against all others, NaN
+/// wins over everything.
+fn extremum(name: &str, args: &[RuntimeValue], wanted: Ordering) -> BuiltinResult {
+	let (first, rest) = args
+		.split_first()
+		.ok_or_else(|| RuntimeError::TooFewArguments(Box::from(name), 1, 0))?;
+
+	let mut best = expect_number(name, first)?;
+	for arg in rest {
+		let number = expect_number(name, arg)?;
+		match number.compare(best)? {
+			Some(ordering) if ordering == wanted => best = number,
+			Some(_) => (),
+			None => return Ok(RuntimeValue::Number(Numeric::Float(f64::NAN))),
+		}
+	}
+	Ok(RuntimeValue::Number(best.to_owned()))
+}
+
+fn min(args: &[RuntimeValue]) -> BuiltinResult {
+	extremum("math.min", args, Ordering::Less)
+}
+
+fn max(args: &[RuntimeValue]) -> BuiltinResult {
+	extremum("math.max", args, Ordering::Greater)
+}
+
+fn clamp(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "math.clamp";
+	let [x, low, high] = expect_args(name, args)?;
+	let (x, low, high) = (
+		expect_number(name, x)?,
+		expect_number(name, low)?,
+		expect_number(name, high)?,
+	);
+
+	if low.compare(high)? == Some(Ordering::Greater) {
+		return Err(RuntimeError::InvalidArgument(
+			Box::from(name),
+			RuntimeValue::Number(low.to_owned()),
+		));
+	}
+
+	let clamped = if x.compare(low)? == Some(Ordering::Less) {
+		low
+	} else if x.compare(high)? == Some(Ordering::Greater) {
+		high
+	} else {
+		x
+	};
+	Ok(RuntimeValue::Number(clamped.to_owned()))
+}
+
+fn sin(args: &[RuntimeValue]) -> BuiltinResult {
+	float_unary("math.sin", args, f64::sin)
+}
+
+fn cos(args: &[RuntimeValue]) -> BuiltinResult {
+	float_unary("math.cos", args, f64::cos)
+}
+
+fn tan(args: &[RuntimeValue]) -> BuiltinResult {
+	float_unary("math.tan", args, f64::tan)
+}
+
+fn atan2(args: &[RuntimeValue]) -> BuiltinResult {
+	let [y, x] = expect_args("math.atan2", args)?;
+	let (y, x) = (
+		expect_dimensionless("math.atan2", y)?,
+		expect_dimensionless("math.atan2", x)?,
+	);
+	Ok(RuntimeValue::Number(Numeric::Float(
+		y.to_f64().atan2(x.to_f64()),
+	)))
+}
+
+fn ln(args: &[RuntimeValue]) -> BuiltinResult {
+	float_unary("math.ln", args, f64::ln)
+}
+
+fn log10(args: &[RuntimeValue]) -> BuiltinResult {
+	float_unary("math.log10", args, f64::log10)
+}
+
+fn exp(args: &[RuntimeValue]) -> BuiltinResult {
+	float_unary("math.exp", args, f64::exp)
+}
+
+fn integer_binary(
+	name: &str,
+	args: &[RuntimeValue],
+	op: fn(Numeric, Numeric) -> Option<Numeric>,
+) -> BuiltinResult {
+	let [x, y] = expect_args(name, args)?;
+	let (x, y) = (expect_number(name, x)?, expect_number(name, y)?);
+	let invalid = if x.is_integer() { y } else { x };
+	op(x.to_owned(), y.to_owned())
+		.map(RuntimeValue::Number)
+		.ok_or_else(|| {
+			RuntimeError::InvalidArgument(Box::from(name), RuntimeValue::Number(invalid.to_owned()))
+		})
+}
+
+fn gcd(args: &[RuntimeValue]) -> BuiltinResult {
+	integer_binary("math.gcd", args, Numeric::gcd)
+}
+
+fn lcm(args: &[RuntimeValue]) -> BuiltinResult {
+	integer_binary("math.lcm", args, Numeric::lcm)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts;
+
+	use crate::{
+		numeric::Numeric,
+		runtime::{error::RuntimeError, value::RuntimeValue},
+	};
+
+	use super::{abs, max, min, BuiltinResult, Member, MATH};
+
+	fn numbers(values: &[Numeric]) -> Vec<RuntimeValue> {
+		values.iter().cloned().map(RuntimeValue::Number).collect()
+	}
+
+	fn call(function: fn(&[RuntimeValue]) -> BuiltinResult, args: &[Numeric]) -> String {
+		function(&numbers(args)).unwrap().to_string()
+	}
+
+	#[test]
+	fn constants() {
+		let constant = |name| match MATH.members.get(name) {
+			Some(Member::Constant(Numeric::Float(value))) => *value,
+			_ => panic!("math.{name} is not a float constant"),
+		};
+		assert_eq!(constant("pi"), consts::PI);
+		assert_eq!(constant("e"), consts::E);
+		assert_eq!(constant("inf"), f64::INFINITY);
+		assert!(constant("nan").is_nan());
+	}
+
+	#[test]
+	fn min_and_max_compare_across_types() {
+		let args = [Numeric::Int(3), Numeric::Float(2.5), Numeric::Int(7)];
+		assert_eq!(call(min, &args), "2.5");
+		assert_eq!(call(max, &args), "7");
+		assert_eq!(call(min, &[Numeric::Int(4)]), "4");
+		let with_nan = [Numeric::Int(1), Numeric::Float(f64::NAN)];
+		assert_eq!(call(max, &with_nan), "math.nan");
+	}
+
+	#[test]
+	fn abs_keeps_the_type() {
+		assert_eq!(call(abs, &[Numeric::Int(-3)]), "3");
+		assert_eq!(call(abs, &[Numeric::Float(-0.5)]), "0.5");
+		assert_eq!(
+			call(abs, &[Numeric::Int(i128::MIN)]),
+			"170141183460469231731687303715884105728"
+		);
+	}
+
+	#[test]
+	fn arity_errors() {
+		assert!(matches!(
+			min(&[]),
+			Err(RuntimeError::TooFewArguments(name, 1, 0)) if &*name == "math.min"
+		));
+		assert_eq!(
+			max(&[]).unwrap_err().to_string(),
+			"Function 'math.max' expects at least 1 argument, got 0"
+		);
+		assert!(matches!(
+			abs(&numbers(&[Numeric::Int(1), Numeric::Int(2)])),
+			Err(RuntimeError::WrongArgumentCount(name, 1, 2)) if &*name == "math.abs"
+		));
+	}
+}
@@ -0,0 +1,183 @@
+mod bytes;
+mod character;
+mod compare;
+mod decimal;
+mod format;
+mod iterator;
+mod math;
+mod regex;
+mod string;
+
+use crate::{
+	environment::Env,
+	numeric::Numeric,
+	runtime::{error::RuntimeError, value::RuntimeValue, variable::RuntimeVariable},
+};
+
+pub type BuiltinResult = Result<RuntimeValue, RuntimeError>;
+
+#[derive(Debug, Clone, Copy)]
+enum BuiltinFn {
+	Pure(fn(&[RuntimeValue]) -> BuiltinResult),
+	/// Reads variables from the environment of the call.
+	Scoped(fn(&Env, &[RuntimeValue]) -> BuiltinResult),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Builtin {
+	pub name: &'static str,
+	func: BuiltinFn,
+}
+
+impl PartialEq for Builtin {
+	fn eq(&self, other: &Self) -> bool {
+		self.name == other.name
+	}
+}
+
+impl Builtin {
+	const fn new(name: &'static str, func: BuiltinFn) -> Self {
+		Self { name, func }
+	}
+
+	pub fn call(&self, env: &Env, args: &[RuntimeValue]) -> BuiltinResult {
+		match self.func {
+			BuiltinFn::Pure(func) => func(args),
+			BuiltinFn::Scoped(func) => func(env, args),
+		}
+	}
+}
+
+#[derive(Debug)]
+pub enum Member {
+	Function(Builtin),
+	Constant(Numeric),
+	Namespace(&'static Namespace),
+}
+
+impl Member {
+	const fn function(name: &'static str, func: fn(&[RuntimeValue]) -> BuiltinResult) -> Self {
+		Self::Function(Builtin::new(name, BuiltinFn::Pure(func)))
+	}
+
+	const fn scoped_function(
+		name: &'static str,
+		func: fn(&Env, &[RuntimeValue]) -> BuiltinResult,
+	) -> Self {
+		Self::Function(Builtin::new(name, BuiltinFn::Scoped(func)))
+	}
+
+	fn value(&self) -> RuntimeValue {
+		match self {
+			Member::Function(builtin) => RuntimeValue::Function(*builtin),
+			Member::Constant(number) => RuntimeValue::Number(number.to_owned()),
+			Member::Namespace(namespace) => RuntimeValue::Namespace(namespace),
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct Namespace {
+	pub name: &'static str,
+	members: phf::Map<&'static str, Member>,
+}
+
+impl PartialEq for Namespace {
+	fn eq(&self, other: &Self) -> bool {
+		self.name == other.name
+	}
+}
+
+impl Namespace {
+	pub fn get(&self, ident: &str) -> Option<RuntimeValue> {
+		self.members.get(ident).map(Member::value)
+	}
+
+	fn function(&self, ident: &str) -> Option<Builtin> {
+		match self.members.get(ident)? {
+			Member::Function(builtin) => Some(*builtin),
+			Member::Constant(_) | Member::Namespace(_) => None,
+		}
+	}
+}
+
+static GLOBALS: Namespace = Namespace {
+	name: "global",
+	members: phf::phf_map! {
+		"rem" => Member::function("rem", math::rem),
+		"compare" => Member::function("compare", compare::compare),
+		"format_number" => Member::function("format_number", format::format_number),
+		"format" => Member::scoped_function("format", format::format),
+		"math" => Member::Namespace(&math::MATH),
+		"decimal" => Member::Namespace(&decimal::DECIMAL),
+		"string" => Member::Namespace(&string::STRING),
+		"char" => Member::Namespace(&character::CHAR),
+		"regex" => Member::Namespace(&regex::REGEX),
+		"bytes" => Member::Namespace(&bytes::BYTES),
+		"iterator" => Member::Namespace(&iterator::ITERATOR),
+	},
+};
+
+/// Looks up `value.ident(..)` method calls, which are the functions of the
+/// namespace for the value's type. Iterable values fall back to the iterator
+/// functions.
+pub fn method(value: &RuntimeValue, ident: &str) -> Option<Builtin> {
+	let iterator = || iterator::ITERATOR.function(ident);
+	match value {
+		RuntimeValue::String(_) => string::STRING.function(ident).or_else(iterator),
+		RuntimeValue::Char(_) => character::CHAR.function(ident),
+		RuntimeValue::Bytes(_) => bytes::BYTES.function(ident).or_else(iterator),
+		RuntimeValue::List(_)
+		| RuntimeValue::Tuple(_)
+		| RuntimeValue::Range(_)
+		| RuntimeValue::Iterator(_) => iterator(),
+		_ => None,
+	}
+}
+
+pub fn declare_globals(env: &Env) {
+	for (ident, member) in GLOBALS.members.entries() {
+		env.declare(ident, RuntimeVariable::from(member.value()));
+	}
+}
+
+fn expect_args<'a, const N: usize>(
+	name: &str,
+	args: &'a [RuntimeValue],
+) -> Result<&'a [RuntimeValue; N], RuntimeError> {
+	args.try_into()
+		.map_err(|_| RuntimeError::WrongArgumentCount(Box::from(name), N, args.len()))
+}
+
+fn expect_number<'a>(name: &str, arg: &'a RuntimeValue) -> Result<&'a Numeric, RuntimeError> {
+	match arg {
+		RuntimeValue::Number(number) => Ok(number),
+		other => Err(RuntimeError::InvalidArgument(
+			Box::from(name),
+			other.to_owned(),
+		)),
+	}
+}
+
+fn expect_dimensionless<'a>(
+	name: &str,
+	arg: &'a RuntimeValue,
+) -> Result<&'a Numeric, RuntimeError> {
+	match expect_number(name, arg)? {
+		number if number.is_quantity() => Err(RuntimeError::InvalidArgument(
+			Box::from(name),
+			arg.to_owned(),
+		)),
+		number => Ok(number),
+	}
+}
+
+fn expect_string<'a>(name: &str, arg: &'a RuntimeValue) -> Result<&'a str, RuntimeError> {
+	match arg {
+		RuntimeValue::String(st) => Ok(st),
+		other => Err(RuntimeError::InvalidArgument(
+			Box::from(name),
+			other.to_owned(),
+		)),
+	}
+}
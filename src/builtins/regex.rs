@@ -0,0 +1,127 @@
+use std::rc::Rc;
+
+use crate::{
+	numeric::Numeric,
+	regex::{Captures, Regex},
+	runtime::{error::RuntimeError, value::RuntimeValue},
+};
+
+use super::{expect_args, expect_string, BuiltinResult, Member, Namespace};
+
+/// Compiles patterns once so they can be reused. The string methods taking a
+/// pattern also accept one as a string, which is compiled on every call.
+pub(super) static REGEX: Namespace = Namespace {
+	name: "regex",
+	members: phf::phf_map! {
+		"compile" => Member::function("regex.compile", compile),
+	},
+};
+
+fn compile(args: &[RuntimeValue]) -> BuiltinResult {
+	let [pattern] = expect_args("regex.compile", args)?;
+	let pattern = expect_string("regex.compile", pattern)?;
+	Ok(RuntimeValue::Regex(Rc::new(Regex::new(pattern)?)))
+}
+
+fn expect_regex(name: &str, arg: &RuntimeValue) -> Result<Rc<Regex>, RuntimeError> {
+	match arg {
+		RuntimeValue::Regex(regex) => Ok(Rc::clone(regex)),
+		RuntimeValue::String(pattern) => Ok(Rc::new(Regex::new(pattern)?)),
+		other => Err(RuntimeError::InvalidArgument(
+			Box::from(name),
+			other.to_owned(),
+		)),
+	}
+}
+
+fn group(text: &str, captures: &Captures, index: usize) -> RuntimeValue {
+	match &captures[index] {
+		Some(range) => RuntimeValue::String(text[range.to_owned()].to_owned()),
+		None => RuntimeValue::Unit,
+	}
+}
+
+/// `s.match(pattern)` tells whether the pattern matches anywhere in `s`, use
+/// `^` and `$` to match all of it.
+pub(super) fn is_match(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.match";
+	let [st, pattern] = expect_args(name, args)?;
+	let (st, regex) = (expect_string(name, st)?, expect_regex(name, pattern)?);
+	Ok(RuntimeValue::Number(Numeric::Int(
+		regex.is_match(st) as i128
+	)))
+}
+
+pub(super) fn find_all(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.find_all";
+	let [st, pattern] = expect_args(name, args)?;
+	let (st, regex) = (expect_string(name, st)?, expect_regex(name, pattern)?);
+	Ok(RuntimeValue::List(
+		regex
+			.captures_iter(st)
+			.map(|captures| group(st, &captures, 0))
+			.collect(),
+	))
+}
+
+/// `s.captures(pattern)` lists the whole first match and its groups, with `_`
+/// for groups that didn't match. `s.captures(pattern, group)` only gives the
+/// group with that name or index. Both give `_` if there is no match.
+pub(super) fn captures(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.captures";
+	let (st, pattern, group_arg) = match args {
+		[st, pattern] => (st, pattern, None),
+		[st, pattern, group] => (st, pattern, Some(group)),
+		_ => {
+			return Err(RuntimeError::WrongArgumentCount(
+				Box::from(name),
+				2,
+				args.len(),
+			))
+		}
+	};
+	let (st, regex) = (expect_string(name, st)?, expect_regex(name, pattern)?);
+
+	let index = match group_arg {
+		None => None,
+		Some(arg) => Some(
+			match arg {
+				RuntimeValue::String(group) => regex.group_index(group),
+				RuntimeValue::Number(Numeric::Int(index)) => usize::try_from(*index)
+					.ok()
+					.filter(|&index| index < regex.group_count()),
+				_ => None,
+			}
+			.ok_or_else(|| RuntimeError::InvalidArgument(Box::from(name), arg.to_owned()))?,
+		),
+	};
+
+	let Some(captures) = regex.captures_at(st, 0) else {
+		return Ok(RuntimeValue::Unit);
+	};
+	Ok(match index {
+		Some(index) => group(st, &captures, index),
+		None => RuntimeValue::List(
+			(0..captures.len())
+				.map(|index| group(st, &captures, index))
+				.collect(),
+		),
+	})
+}
+
+pub(super) fn replace(name: &str, st: &str, regex: &Regex, to: &RuntimeValue) -> BuiltinResult {
+	regex
+		.replace_all(st, expect_string(name, to)?)
+		.map(RuntimeValue::String)
+		.ok_or_else(|| RuntimeError::InvalidArgument(Box::from(name), to.to_owned()))
+}
+
+pub(super) fn split(st: &str, regex: &Regex) -> RuntimeValue {
+	RuntimeValue::List(
+		regex
+			.split(st)
+			.into_iter()
+			.map(|part| RuntimeValue::String(part.to_owned()))
+			.collect(),
+	)
+}
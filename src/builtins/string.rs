@@ -0,0 +1,206 @@
+use num_bigint::BigInt;
+
+use crate::{
+	helpers::checked_repeat,
+	numeric::Numeric,
+	runtime::{error::RuntimeError, value::RuntimeValue},
+};
+
+use super::{bytes, expect_args, expect_string, regex, BuiltinResult, Member, Namespace};
+
+/// String functions, also callable as methods: `s.len()` is `string.len(s)`.
+/// Lengths and positions count chars, not bytes. `split` and `replace` take
+/// either a plain string or a compiled regex.
+pub(super) static STRING: Namespace = Namespace {
+	name: "string",
+	members: phf::phf_map! {
+		"len" => Member::function("string.len", len),
+		"upper" => Member::function("string.upper", upper),
+		"lower" => Member::function("string.lower", lower),
+		"trim" => Member::function("string.trim", trim),
+		"split" => Member::function("string.split", split),
+		"join" => Member::function("string.join", join),
+		"contains" => Member::function("string.contains", contains),
+		"starts_with" => Member::function("string.starts_with", starts_with),
+		"ends_with" => Member::function("string.ends_with", ends_with),
+		"replace" => Member::function("string.replace", replace),
+		"find" => Member::function("string.find", find),
+		"repeat" => Member::function("string.repeat", repeat),
+		"reverse" => Member::function("string.reverse", reverse),
+		"chars" => Member::function("string.chars", chars),
+		"to_int" => Member::function("string.to_int", to_int),
+		"to_float" => Member::function("string.to_float", to_float),
+		"match" => Member::function("string.match", regex::is_match),
+		"find_all" => Member::function("string.find_all", regex::find_all),
+		"captures" => Member::function("string.captures", regex::captures),
+		"encode" => Member::function("string.encode", bytes::encode),
+	},
+};
+
+fn invalid(name: &str, arg: &RuntimeValue) -> RuntimeError {
+	RuntimeError::InvalidArgument(Box::from(name), arg.to_owned())
+}
+
+fn int(value: usize) -> RuntimeValue {
+	RuntimeValue::Number(Numeric::Int(value as i128))
+}
+
+fn boolean(value: bool) -> RuntimeValue {
+	RuntimeValue::Number(Numeric::Int(value as i128))
+}
+
+fn unary(name: &str, args: &[RuntimeValue], op: fn(&str) -> String) -> BuiltinResult {
+	let [st] = expect_args(name, args)?;
+	Ok(RuntimeValue::String(op(expect_string(name, st)?)))
+}
+
+fn predicate(name: &str, args: &[RuntimeValue], op: fn(&str, &str) -> bool) -> BuiltinResult {
+	let [st, pattern] = expect_args(name, args)?;
+	let (st, pattern) = (expect_string(name, st)?, expect_string(name, pattern)?);
+	Ok(boolean(op(st, pattern)))
+}
+
+fn len(args: &[RuntimeValue]) -> BuiltinResult {
+	let [st] = expect_args("string.len", args)?;
+	Ok(int(expect_string("string.len", st)?.chars().count()))
+}
+
+fn upper(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("string.upper", args, str::to_uppercase)
+}
+
+fn lower(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("string.lower", args, str::to_lowercase)
+}
+
+fn trim(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("string.trim", args, |st| st.trim().to_owned())
+}
+
+/// Code points are reversed, so combining marks end up on the wrong letter.
+fn reverse(args: &[RuntimeValue]) -> BuiltinResult {
+	unary("string.reverse", args, |st| st.chars().rev().collect())
+}
+
+fn split(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.split";
+	let [st, separator_arg] = expect_args(name, args)?;
+	let st = expect_string(name, st)?;
+	if let RuntimeValue::Regex(separator) = separator_arg {
+		return Ok(regex::split(st, separator));
+	}
+	let separator = expect_string(name, separator_arg)?;
+	if separator.is_empty() {
+		return Err(invalid(name, separator_arg));
+	}
+	Ok(RuntimeValue::List(
+		st.split(separator)
+			.map(|part| RuntimeValue::String(part.to_owned()))
+			.collect(),
+	))
+}
+
+/// `separator.join(parts)` concatenates a list of strings and chars.
+fn join(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.join";
+	let [separator, parts_arg] = expect_args(name, args)?;
+	let separator = expect_string(name, separator)?;
+	let RuntimeValue::List(parts) = parts_arg else {
+		return Err(invalid(name, parts_arg));
+	};
+	let parts = parts
+		.iter()
+		.map(|part| match part {
+			RuntimeValue::String(_) | RuntimeValue::Char(_) => Ok(part.to_text()),
+			other => Err(invalid(name, other)),
+		})
+		.collect::<Result<Box<[_]>, _>>()?;
+	Ok(RuntimeValue::String(parts.join(separator)))
+}
+
+fn contains(args: &[RuntimeValue]) -> BuiltinResult {
+	predicate("string.contains", args, |st, pattern| st.contains(pattern))
+}
+
+fn starts_with(args: &[RuntimeValue]) -> BuiltinResult {
+	predicate("string.starts_with", args, |st, pattern| {
+		st.starts_with(pattern)
+	})
+}
+
+fn ends_with(args: &[RuntimeValue]) -> BuiltinResult {
+	predicate("string.ends_with", args, |st, pattern| {
+		st.ends_with(pattern)
+	})
+}
+
+fn replace(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.replace";
+	let [st, from, to] = expect_args(name, args)?;
+	if let RuntimeValue::Regex(from) = from {
+		return regex::replace(name, expect_string(name, st)?, from, to);
+	}
+	let (st, from, to) = (
+		expect_string(name, st)?,
+		expect_string(name, from)?,
+		expect_string(name, to)?,
+	);
+	Ok(RuntimeValue::String(st.replace(from, to)))
+}
+
+/// The char index of the first occurrence, -1 if there is none.
+fn find(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.find";
+	let [st, pattern] = expect_args(name, args)?;
+	let (st, pattern) = (expect_string(name, st)?, expect_string(name, pattern)?);
+	Ok(match st.find(pattern) {
+		Some(idx) => int(st[..idx].chars().count()),
+		None => RuntimeValue::Number(Numeric::Int(-1)),
+	})
+}
+
+fn repeat(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.repeat";
+	let [st, count_arg] = expect_args(name, args)?;
+	let st = expect_string(name, st)?;
+	let repeated = match count_arg {
+		RuntimeValue::Number(Numeric::Int(count)) => usize::try_from(*count).ok(),
+		_ => None,
+	}
+	.and_then(|count| checked_repeat(st, count))
+	.ok_or_else(|| invalid(name, count_arg))?;
+	Ok(RuntimeValue::String(repeated))
+}
+
+fn chars(args: &[RuntimeValue]) -> BuiltinResult {
+	let [st] = expect_args("string.chars", args)?;
+	Ok(RuntimeValue::List(
+		expect_string("string.chars", st)?
+			.chars()
+			.map(RuntimeValue::Char)
+			.collect(),
+	))
+}
+
+/// Parses a decimal integer of any size, surrounding whitespace is ignored.
+fn to_int(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.to_int";
+	let [arg] = expect_args(name, args)?;
+	expect_string(name, arg)?
+		.trim()
+		.parse::<BigInt>()
+		.map(|int| RuntimeValue::Number(Numeric::from(int)))
+		.map_err(|_| invalid(name, arg))
+}
+
+/// Parses a float, including `inf` and `NaN`. Surrounding whitespace is
+/// ignored.
+fn to_float(args: &[RuntimeValue]) -> BuiltinResult {
+	let name = "string.to_float";
+	let [arg] = expect_args(name, args)?;
+	expect_string(name, arg)?
+		.trim()
+		.parse::<f64>()
+		.map(|float| RuntimeValue::Number(Numeric::Float(float)))
+		.map_err(|_| invalid(name, arg))
+}
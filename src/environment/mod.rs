@@ -6,6 +6,7 @@ use std::{
 };
 
 use crate::{
+	builtins,
 	helpers::hashmap_to_string,
 	runtime::{error::RuntimeError::*, variable::RuntimeVariable, RuntimeResult},
 };
@@ -57,7 +58,9 @@ impl Env {
 	}
 
 	pub fn global() -> Self {
-		Self::new_with_parent(None)
+		let env = Self::new_with_parent(None);
+		builtins::declare_globals(&env);
+		env
 	}
 
 	pub fn new(parent: Self) -> Self {
@@ -2,14 +2,20 @@ use std::fmt::Display;
 
 use super::Expression;
 
+/// `ident = value`, or `ident.field = value` to update a field of a struct.
 #[derive(Debug, Clone)]
 pub struct AssignmentExpression {
 	pub ident: Box<str>,
+	pub fields: Vec<Box<str>>,
 	pub value: Box<Expression>,
 }
 
 impl Display for AssignmentExpression {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "{} = {}", self.ident, self.value)
+		f.write_str(&self.ident)?;
+		for field in &self.fields {
+			write!(f, ".{field}")?;
+		}
+		write!(f, " = {}", self.value)
 	}
 }
@@ -8,8 +8,20 @@ pub enum BinaryOp {
 	Subtract,
 	Multiply,
 	Divide,
+	FloorDivide,
 	Modulo,
+	Power,
+	BitAnd,
+	BitOr,
+	BitXor,
+	ShiftLeft,
+	ShiftRight,
 	Equals,
+	NotEquals,
+	Less,
+	LessEqual,
+	Greater,
+	GreaterEqual,
 }
 
 impl Display for BinaryOp {
@@ -21,8 +33,20 @@ impl Display for BinaryOp {
 			Subtract => "-",
 			Multiply => "*",
 			Divide => "/",
+			FloorDivide => "//",
 			Modulo => "%",
+			Power => "**",
+			BitAnd => "&",
+			BitOr => "|",
+			BitXor => "^",
+			ShiftLeft => "<<",
+			ShiftRight => ">>",
 			Equals => "==",
+			NotEquals => "!=",
+			Less => "<",
+			LessEqual => "<=",
+			Greater => ">",
+			GreaterEqual => ">=",
 		})
 	}
 }
@@ -0,0 +1,24 @@
+use std::fmt::Display;
+
+use super::Expression;
+
+#[derive(Debug, Clone)]
+pub struct CallExpression {
+	pub callee: Box<Expression>,
+	pub args: Vec<Expression>,
+}
+
+impl Display for CallExpression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}({})",
+			self.callee,
+			self.args
+				.iter()
+				.map(|arg| arg.to_string())
+				.collect::<Box<[_]>>()
+				.join(", ")
+		)
+	}
+}
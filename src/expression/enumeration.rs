@@ -0,0 +1,50 @@
+use std::fmt::Display;
+
+use crate::lexer::token::Token::Enum;
+
+/// `enum Shape { Circle(r), Rect(w, h), Empty }`, where each variant names
+/// its payload.
+#[derive(Debug, Clone)]
+pub struct EnumDeclaration {
+	pub name: Box<str>,
+	pub variants: Vec<(Box<str>, Vec<Box<str>>)>,
+}
+
+impl Display for EnumDeclaration {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} {} {{", Enum, self.name)?;
+		write_variants(f, &self.variants)
+	}
+}
+
+/// Writes the variants of an enum and the closing brace.
+pub(crate) fn write_variants(
+	f: &mut std::fmt::Formatter<'_>,
+	variants: &[(Box<str>, Vec<Box<str>>)],
+) -> std::fmt::Result {
+	if variants.is_empty() {
+		return f.write_str("}");
+	}
+	let variants = variants
+		.iter()
+		.map(|(ident, payload)| match payload.is_empty() {
+			true => ident.to_string(),
+			false => format!("{ident}({})", payload.join(", ")),
+		})
+		.collect::<Box<[_]>>()
+		.join(", ");
+	write!(f, " {variants} }}")
+}
+
+/// `Shape::Circle`
+#[derive(Debug, Clone)]
+pub struct VariantPath {
+	pub ty: Box<str>,
+	pub variant: Box<str>,
+}
+
+impl Display for VariantPath {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}::{}", self.ty, self.variant)
+	}
+}
@@ -0,0 +1,15 @@
+use std::fmt::Display;
+
+use super::Expression;
+
+#[derive(Debug, Clone)]
+pub struct IndexExpression {
+	pub object: Box<Expression>,
+	pub index: Box<Expression>,
+}
+
+impl Display for IndexExpression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}[{}]", self.object, self.index)
+	}
+}
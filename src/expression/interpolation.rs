@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+use crate::helpers::escape_string_text;
+
+use super::Expression;
+
+#[derive(Debug, Clone)]
+pub enum InterpolationPart {
+	Text(Box<str>),
+	Expression(Expression),
+}
+
+/// A string literal with embedded expressions, such as `"total: {x + 1}"`.
+#[derive(Debug, Clone)]
+pub struct InterpolationExpression {
+	pub parts: Vec<InterpolationPart>,
+}
+
+impl Display for InterpolationExpression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("\"")?;
+		for part in &self.parts {
+			match part {
+				InterpolationPart::Text(text) => f.write_str(&escape_string_text(text))?,
+				InterpolationPart::Expression(expr) => write!(f, "{{{expr}}}")?,
+			}
+		}
+		f.write_str("\"")
+	}
+}
@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+use crate::lexer::token::Token::{For, In};
+
+use super::{pattern::Pattern, Expression};
+
+/// `for pattern in iterable { body }`, where the body is a list of
+/// expressions separated by semicolons.
+#[derive(Debug, Clone)]
+pub struct ForExpression {
+	pub pattern: Pattern,
+	pub iterable: Box<Expression>,
+	pub body: Vec<Expression>,
+}
+
+impl Display for ForExpression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{For} {} {In} {} {{", self.pattern, self.iterable)?;
+		if self.body.is_empty() {
+			return f.write_str("}");
+		}
+		let body = self
+			.body
+			.iter()
+			.map(|expr| expr.to_string())
+			.collect::<Box<[_]>>()
+			.join("; ");
+		write!(f, " {body} }}")
+	}
+}
@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use crate::lexer::token::Token::{FatArrow, If, Match};
+
+use super::{pattern::Pattern, Expression};
+
+/// `pattern if guard => body`, where the guard is optional.
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+	pub pattern: Pattern,
+	pub guard: Option<Expression>,
+	pub body: Expression,
+}
+
+impl Display for MatchArm {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.pattern)?;
+		if let Some(guard) = &self.guard {
+			write!(f, " {If} {guard}")?;
+		}
+		write!(f, " {FatArrow} {}", self.body)
+	}
+}
+
+/// `match value { arm, ... }`, which evaluates the first arm matching the
+/// value.
+#[derive(Debug, Clone)]
+pub struct MatchExpression {
+	pub value: Box<Expression>,
+	pub arms: Vec<MatchArm>,
+}
+
+impl Display for MatchExpression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} {} {{", Match, self.value)?;
+		if self.arms.is_empty() {
+			return f.write_str("}");
+		}
+		let arms = self
+			.arms
+			.iter()
+			.map(|arm| arm.to_string())
+			.collect::<Box<[_]>>()
+			.join(", ");
+		write!(f, " {arms} }}")
+	}
+}
@@ -0,0 +1,15 @@
+use std::fmt::Display;
+
+use super::Expression;
+
+#[derive(Debug, Clone)]
+pub struct MemberExpression {
+	pub object: Box<Expression>,
+	pub ident: Box<str>,
+}
+
+impl Display for MemberExpression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}.{}", self.object, self.ident)
+	}
+}
@@ -1,14 +1,37 @@
 pub mod assignment;
 pub mod binary;
+pub mod call;
 pub mod declaration;
+pub mod enumeration;
+pub mod index;
+pub mod interpolation;
+pub mod looping;
+pub mod matching;
+pub mod member;
+pub mod pattern;
+pub mod range;
+pub mod structure;
 pub mod unary;
 
 use assignment::AssignmentExpression;
 use binary::BinaryExpression;
+use call::CallExpression;
 use declaration::DeclarationExpression;
+use enumeration::{EnumDeclaration, VariantPath};
+use index::IndexExpression;
+use interpolation::InterpolationExpression;
+use looping::ForExpression;
+use matching::MatchExpression;
+use member::MemberExpression;
+use range::RangeExpression;
+use structure::{StructDeclaration, StructLiteral};
 use unary::UnaryExpression;
 
-use crate::numeric::Numeric;
+use crate::{
+	helpers::{escape_bytes, tuple_to_string},
+	lexer::token::Token,
+	numeric::Numeric,
+};
 use std::fmt::{Debug, Display};
 
 #[derive(Debug, Clone)]
@@ -16,12 +39,29 @@ pub enum Expression {
 	Program(Vec<Expression>),
 	LiteralNumber(Numeric),
 	LiteralString(Box<str>),
+	LiteralChar(char),
+	LiteralBytes(Vec<u8>),
+	Interpolation(InterpolationExpression),
 	Identifier(Box<str>),
 	Unary(UnaryExpression),
 	Binary(BinaryExpression),
 	Unit,
 	Assignment(AssignmentExpression),
 	Declaration(DeclarationExpression),
+	Call(CallExpression),
+	Member(MemberExpression),
+	Index(IndexExpression),
+	List(Vec<Expression>),
+	Tuple(Vec<Expression>),
+	StructDeclaration(StructDeclaration),
+	StructLiteral(StructLiteral),
+	EnumDeclaration(EnumDeclaration),
+	VariantPath(VariantPath),
+	Match(MatchExpression),
+	Range(RangeExpression),
+	For(ForExpression),
+	Break,
+	Continue,
 }
 
 impl Display for Expression {
@@ -32,12 +72,37 @@ impl Display for Expression {
 			Program(exprs) => exprs.iter().try_for_each(|e| writeln!(f, "{e}")),
 			LiteralNumber(num) => Display::fmt(num, f),
 			LiteralString(st) => Debug::fmt(st, f),
+			LiteralChar(ch) => Debug::fmt(ch, f),
+			LiteralBytes(bytes) => write!(f, "b\"{}\"", escape_bytes(bytes)),
+			Interpolation(interpolation) => Display::fmt(interpolation, f),
 			Identifier(ident) => f.write_str(ident),
 			Unary(unary) => Display::fmt(unary, f),
 			Binary(binary) => Display::fmt(binary, f),
 			Unit => f.write_str("_"),
 			Assignment(assignment) => Display::fmt(assignment, f),
 			Declaration(declaration) => Display::fmt(declaration, f),
+			Call(call) => Display::fmt(call, f),
+			Member(member) => Display::fmt(member, f),
+			Index(index) => Display::fmt(index, f),
+			List(items) => write!(
+				f,
+				"[{}]",
+				items
+					.iter()
+					.map(|item| item.to_string())
+					.collect::<Box<[_]>>()
+					.join(", ")
+			),
+			Tuple(items) => f.write_str(&tuple_to_string(items)),
+			StructDeclaration(declaration) => Display::fmt(declaration, f),
+			StructLiteral(literal) => Display::fmt(literal, f),
+			EnumDeclaration(declaration) => Display::fmt(declaration, f),
+			VariantPath(path) => Display::fmt(path, f),
+			Match(matching) => Display::fmt(matching, f),
+			Range(range) => Display::fmt(range, f),
+			For(looping) => Display::fmt(looping, f),
+			Break => Display::fmt(&Token::Break, f),
+			Continue => Display::fmt(&Token::Continue, f),
 		}
 	}
 }
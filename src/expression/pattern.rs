@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+use crate::{
+	helpers::tuple_to_string,
+	lexer::token::Token::{DotDot, DotDotEqual, Mutable},
+};
+
+use super::{enumeration::VariantPath, Expression};
+
+/// The left side of a `let` or of a `match` arm, which binds names to the
+/// parts of a value.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+	/// A name, which is mutable when written with `~`.
+	Identifier(Box<str>, bool),
+	/// `_`, which matches anything and binds nothing.
+	Wildcard,
+	Tuple(Vec<Pattern>),
+	List(Vec<Pattern>),
+	/// A number, string, char or bytes literal, which matches equal values.
+	Literal(Box<Expression>),
+	/// `low..high` or `low..=high` between two literals, inclusive if the
+	/// flag is set.
+	Range(Box<Expression>, Box<Expression>, bool),
+	/// `Shape::Circle(r)`, or `Shape::Empty` for a variant without payload.
+	Variant(VariantPath, Vec<Pattern>),
+}
+
+impl Pattern {
+	/// Whether the pattern matches any value of the right shape.
+	pub fn is_irrefutable(&self) -> bool {
+		match self {
+			Pattern::Identifier(..) | Pattern::Wildcard => true,
+			Pattern::Tuple(items) => items.iter().all(Pattern::is_irrefutable),
+			_ => false,
+		}
+	}
+}
+
+impl Display for Pattern {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Pattern::Identifier(ident, true) => write!(f, "{Mutable}{ident}"),
+			Pattern::Identifier(ident, false) => f.write_str(ident),
+			Pattern::Wildcard => f.write_str("_"),
+			Pattern::Tuple(items) => f.write_str(&tuple_to_string(items)),
+			Pattern::List(items) => write!(
+				f,
+				"[{}]",
+				items
+					.iter()
+					.map(|item| item.to_string())
+					.collect::<Box<[_]>>()
+					.join(", ")
+			),
+			Pattern::Literal(literal) => Display::fmt(literal, f),
+			Pattern::Range(low, high, true) => write!(f, "{low}{DotDotEqual}{high}"),
+			Pattern::Range(low, high, false) => write!(f, "{low}{DotDot}{high}"),
+			Pattern::Variant(path, payload) if payload.is_empty() => Display::fmt(path, f),
+			Pattern::Variant(path, payload) => write!(
+				f,
+				"{path}({})",
+				payload
+					.iter()
+					.map(|item| item.to_string())
+					.collect::<Box<[_]>>()
+					.join(", ")
+			),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Pattern::{self, *};
+	use crate::{
+		expression::{enumeration::VariantPath, Expression},
+		numeric::Numeric,
+	};
+
+	fn name(ident: &str) -> Pattern {
+		Identifier(Box::from(ident), false)
+	}
+
+	fn literal(value: i128) -> Pattern {
+		Literal(Box::new(Expression::LiteralNumber(Numeric::Int(value))))
+	}
+
+	#[test]
+	fn names_wildcards_and_their_tuples_are_irrefutable() {
+		assert!(name("x").is_irrefutable());
+		assert!(Identifier(Box::from("x"), true).is_irrefutable());
+		assert!(Wildcard.is_irrefutable());
+		assert!(Tuple(vec![name("a"), Tuple(vec![Wildcard, name("b")])]).is_irrefutable());
+	}
+
+	#[test]
+	fn other_patterns_are_refutable() {
+		let range = Range(
+			Box::new(Expression::LiteralNumber(Numeric::Int(0))),
+			Box::new(Expression::LiteralNumber(Numeric::Int(9))),
+			true,
+		);
+		let variant = Variant(
+			VariantPath {
+				ty: Box::from("Shape"),
+				variant: Box::from("Circle"),
+			},
+			vec![name("r")],
+		);
+		for pattern in [literal(1), range, variant, List(vec![name("a")])] {
+			assert!(!pattern.is_irrefutable(), "{pattern}");
+		}
+		assert!(!Tuple(vec![name("a"), literal(1)]).is_irrefutable());
+	}
+}
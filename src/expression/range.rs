@@ -0,0 +1,25 @@
+use std::fmt::Display;
+
+use crate::lexer::token::Token::{DotDot, DotDotEqual};
+
+use super::Expression;
+
+/// `start..end` or `start..=end`, optionally followed by `step n`.
+#[derive(Debug, Clone)]
+pub struct RangeExpression {
+	pub start: Box<Expression>,
+	pub end: Box<Expression>,
+	pub step: Option<Box<Expression>>,
+	pub inclusive: bool,
+}
+
+impl Display for RangeExpression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let dots = if self.inclusive { DotDotEqual } else { DotDot };
+		write!(f, "{}{dots}{}", self.start, self.end)?;
+		if let Some(step) = &self.step {
+			write!(f, " step {step}")?;
+		}
+		Ok(())
+	}
+}
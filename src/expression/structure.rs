@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use crate::lexer::token::Token::Struct;
+
+use super::Expression;
+
+/// `struct Point { x: int, y: int }`, where each field names its type.
+#[derive(Debug, Clone)]
+pub struct StructDeclaration {
+	pub name: Box<str>,
+	pub fields: Vec<(Box<str>, Box<str>)>,
+}
+
+impl Display for StructDeclaration {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} {} {{", Struct, self.name)?;
+		write_fields(f, &self.fields)
+	}
+}
+
+/// `Point { x: 1, y: 2 }`
+#[derive(Debug, Clone)]
+pub struct StructLiteral {
+	pub name: Box<str>,
+	pub fields: Vec<(Box<str>, Expression)>,
+}
+
+impl Display for StructLiteral {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} {{", self.name)?;
+		write_fields(f, &self.fields)
+	}
+}
+
+/// Writes the fields of a struct and the closing brace.
+pub(crate) fn write_fields<V: Display>(
+	f: &mut std::fmt::Formatter<'_>,
+	fields: &[(impl Display, V)],
+) -> std::fmt::Result {
+	if fields.is_empty() {
+		return f.write_str("}");
+	}
+	let fields = fields
+		.iter()
+		.map(|(ident, value)| format!("{ident}: {value}"))
+		.collect::<Box<[_]>>()
+		.join(", ");
+	write!(f, " {fields} }}")
+}
@@ -6,6 +6,7 @@ use super::Expression;
 pub enum UnaryOp {
 	Plus,
 	Minus,
+	BitNot,
 }
 
 impl Display for UnaryOp {
@@ -15,6 +16,7 @@ impl Display for UnaryOp {
 		f.write_str(match self {
 			Plus => "+",
 			Minus => "-",
+			BitNot => "!",
 		})
 	}
 }
@@ -22,3 +22,71 @@ where
 			.join(",\n")
 	)
 }
+
+/// Writes items in parentheses, with a trailing comma after a single one so
+/// it isn't read as a grouping.
+pub(crate) fn tuple_to_string<T: Display>(items: &[T]) -> String {
+	match items {
+		[item] => format!("({item},)"),
+		items => format!(
+			"({})",
+			items
+				.iter()
+				.map(|item| item.to_string())
+				.collect::<Box<[_]>>()
+				.join(", ")
+		),
+	}
+}
+
+/// Longest string, in bytes, that repeating a string may produce.
+const MAX_REPEAT_LEN: usize = 1 << 30;
+
+/// `text` repeated `count` times, `None` if the result would be longer than
+/// `MAX_REPEAT_LEN`.
+pub(crate) fn checked_repeat(text: &str, count: usize) -> Option<String> {
+	text.len()
+		.checked_mul(count)
+		.filter(|&len| len <= MAX_REPEAT_LEN)
+		.map(|_| text.repeat(count))
+}
+
+/// Escapes text so it can be written back between the quotes of an
+/// interpolated string literal, where braces are doubled.
+pub(crate) fn escape_string_text(text: &str) -> String {
+	let mut escaped = String::new();
+	for ch in text.chars() {
+		match ch {
+			'"' | '\\' => {
+				escaped.push('\\');
+				escaped.push(ch);
+			}
+			'{' | '}' => {
+				escaped.push(ch);
+				escaped.push(ch);
+			}
+			'\n' => escaped.push_str("\\n"),
+			'\r' => escaped.push_str("\\r"),
+			'\t' => escaped.push_str("\\t"),
+			other => escaped.push(other),
+		}
+	}
+	escaped
+}
+
+/// Escapes bytes so they can be written back between the quotes of a byte
+/// string literal. Printable ASCII is kept, anything else becomes `\xHH`.
+pub(crate) fn escape_bytes(bytes: &[u8]) -> String {
+	let mut escaped = String::new();
+	for &byte in bytes {
+		match byte {
+			b'"' | b'\\' => {
+				escaped.push('\\');
+				escaped.push(char::from(byte));
+			}
+			b' '..=b'~' => escaped.push(char::from(byte)),
+			other => escaped.push_str(&format!("\\x{other:02x}")),
+		}
+	}
+	escaped
+}
@@ -1,10 +1,64 @@
 use std::fmt::Display;
 
+#[derive(Debug, Clone, Copy)]
+pub struct Position {
+	pub line: usize,
+	pub column: usize,
+}
+
+impl Position {
+	/// The position of the byte `offset` in `source`.
+	pub fn of(source: &str, offset: usize) -> Self {
+		let before = &source[..offset];
+		Self {
+			line: before.matches('\n').count() + 1,
+			column: before.chars().rev().take_while(|&ch| ch != '\n').count() + 1,
+		}
+	}
+}
+
+impl Display for Position {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "line {}, column {}", self.line, self.column)
+	}
+}
+
 #[derive(Debug, Clone)]
 pub enum LexerError {
 	UnexpectedChar(char),
 	UnclosedString(Box<str>),
-	UnsupportedEscape(char),
+	InvalidEscape(Box<str>, Position),
+	MissingDigits(Box<str>),
+	InvalidDigit(char, u32),
+	MisplacedSeparator(Box<str>),
+	MissingExponent(Box<str>),
+	UnclosedInterpolation(Box<str>),
+	InvalidChar(Box<str>),
+}
+
+impl LexerError {
+	/// Turns a position in a snippet of the source into one in the whole
+	/// source. The snippet starts at `origin`, and `indent` characters were
+	/// removed from the start of all its lines but the first.
+	pub fn relocated(self, origin: Position, indent: usize) -> Self {
+		match self {
+			LexerError::InvalidEscape(escape, position) => {
+				let column = if position.line == 1 {
+					position.column + origin.column - 1
+				} else {
+					position.column + indent
+				};
+				LexerError::InvalidEscape(
+					escape,
+					Position {
+						line: position.line + origin.line - 1,
+						column,
+					},
+				)
+			}
+			other => other,
+		}
+	}
 }
 
 impl Display for LexerError {
@@ -14,7 +68,19 @@ impl Display for LexerError {
 		match self {
 			UnexpectedChar(ch) => write!(f, "Unexpected character: '{ch}'"),
 			UnclosedString(st) => write!(f, "Unclosed string literal: \"{st:?}",),
-			UnsupportedEscape(ch) => write!(f, "Unsupported escape sequence: \\{ch}"),
+			InvalidEscape(escape, position) => {
+				write!(f, "Invalid escape sequence {escape} at {position}")
+			}
+			MissingDigits(lit) => write!(f, "Missing digits in number literal: {lit}"),
+			InvalidDigit(ch, radix) => {
+				write!(f, "Invalid digit '{ch}' in base {radix} number literal")
+			}
+			MisplacedSeparator(lit) => {
+				write!(f, "Digit separator must be between digits: {lit}")
+			}
+			MissingExponent(lit) => write!(f, "Missing exponent in number literal: {lit}"),
+			InvalidChar(lit) => write!(f, "Invalid char literal: {lit}"),
+			UnclosedInterpolation(src) => write!(f, "Unclosed interpolation in string: {{{src}"),
 		}
 	}
 }
@@ -1,13 +1,23 @@
 pub mod error;
 pub mod token;
 
-use error::LexerError;
-use token::Token;
+use std::{iter::Peekable, str::Chars};
+
+use error::{LexerError, Position};
+use token::{NumberKind, StringPart, Token};
 
 pub struct Lexer;
 
 static KEYWORDS: phf::Map<&str, Token> = phf::phf_map! {
 	"let" => Token::Let,
+	"struct" => Token::Struct,
+	"enum" => Token::Enum,
+	"match" => Token::Match,
+	"if" => Token::If,
+	"for" => Token::For,
+	"in" => Token::In,
+	"break" => Token::Break,
+	"continue" => Token::Continue,
 	"_" => Token::Unit,
 };
 
@@ -16,6 +26,427 @@ impl Lexer {
 		matches!(ch, '0'..='9' | 'a'..='z' | 'A'..='Z' | '_')
 	}
 
+	fn is_identifier_start(ch: &char) -> bool {
+		matches!(ch, 'a'..='z' | 'A'..='Z' | '_')
+	}
+
+	fn radix_prefix(ch: &char) -> Option<u32> {
+		match ch {
+			'x' | 'X' => Some(16),
+			'o' | 'O' => Some(8),
+			'b' | 'B' => Some(2),
+			_ => None,
+		}
+	}
+
+	/// Consumes digits in the given radix into `digits`, skipping `_`
+	/// separators, which are only allowed between two digits.
+	fn lex_digits(
+		chars: &mut Peekable<Chars>,
+		literal: &mut String,
+		digits: &mut String,
+		radix: u32,
+	) -> Result<(), LexerError> {
+		use LexerError::*;
+
+		let mut after_separator = false;
+		while let Some(ch) = chars.next_if(|ch| {
+			ch.is_digit(radix) || *ch == '_' || (radix != 10 && ch.is_ascii_alphanumeric())
+		}) {
+			literal.push(ch);
+			if ch == '_' {
+				if digits.is_empty() || after_separator {
+					return Err(MisplacedSeparator(literal.as_str().into()));
+				}
+				after_separator = true;
+			} else if ch.is_digit(radix) {
+				digits.push(ch);
+				after_separator = false;
+			} else {
+				return Err(InvalidDigit(ch, radix));
+			}
+		}
+
+		if after_separator {
+			Err(MisplacedSeparator(literal.as_str().into()))
+		} else {
+			Ok(())
+		}
+	}
+
+	fn lex_number(first: char, chars: &mut Peekable<Chars>) -> Result<Token, LexerError> {
+		use LexerError::*;
+
+		let mut literal = first.to_string();
+		let mut digits = first.to_string();
+
+		if first == '0' {
+			if let Some(radix) = chars.peek().and_then(Self::radix_prefix) {
+				literal.extend(chars.next());
+				digits.clear();
+				Self::lex_digits(chars, &mut literal, &mut digits, radix)?;
+				if digits.is_empty() {
+					return Err(MissingDigits(literal.into_boxed_str()));
+				}
+				return Ok(Token::LiteralNumber(
+					digits.into_boxed_str(),
+					NumberKind::Int(radix),
+				));
+			}
+		}
+
+		let mut is_float = first == '.';
+		Self::lex_digits(chars, &mut literal, &mut digits, 10)?;
+
+		// `1..2` and `1.abs` must not swallow the dot
+		let dot_follows = !is_float
+			&& chars.peek() == Some(&'.')
+			&& chars
+				.clone()
+				.nth(1)
+				.is_none_or(|ne| ne != '.' && !Self::is_identifier_start(&ne));
+		if dot_follows {
+			literal.extend(chars.next());
+			digits.push('.');
+			is_float = true;
+			Self::lex_digits(chars, &mut literal, &mut digits, 10)?;
+		}
+
+		if let Some(e) = chars.next_if(|ch| matches!(ch, 'e' | 'E')) {
+			literal.push(e);
+			digits.push('e');
+			if let Some(sign) = chars.next_if(|ch| matches!(ch, '+' | '-')) {
+				literal.push(sign);
+				digits.push(sign);
+			}
+			if !chars.peek().is_some_and(char::is_ascii_digit) {
+				return Err(MissingExponent(literal.into_boxed_str()));
+			}
+			is_float = true;
+			Self::lex_digits(chars, &mut literal, &mut digits, 10)?;
+		}
+
+		let kind = if chars.next_if_eq(&'r').is_some() {
+			NumberKind::Rational
+		} else if chars.next_if_eq(&'d').is_some() {
+			NumberKind::Decimal
+		} else if is_float {
+			NumberKind::Float
+		} else {
+			NumberKind::Int(10)
+		};
+
+		Ok(Token::LiteralNumber(digits.into_boxed_str(), kind))
+	}
+
+	/// Collects the source of an expression embedded in a string up to the
+	/// matching `}`, skipping over braces inside nested string literals.
+	fn interpolation_source(chars: &mut Peekable<Chars>) -> Result<String, LexerError> {
+		let mut source = String::new();
+		let mut depth = 0;
+		let mut in_string = false;
+		let mut escaped = false;
+		for ch in chars.by_ref() {
+			if in_string {
+				match ch {
+					_ if escaped => escaped = false,
+					'\\' => escaped = true,
+					'"' => in_string = false,
+					_ => (),
+				}
+			} else {
+				match ch {
+					'"' => in_string = true,
+					'{' => depth += 1,
+					'}' if depth == 0 => return Ok(source),
+					'}' => depth -= 1,
+					'\n' => break,
+					_ => (),
+				}
+			}
+			source.push(ch);
+		}
+		Err(LexerError::UnclosedInterpolation(source.into_boxed_str()))
+	}
+
+	/// Byte offset of the iterator in `source`, which it has to be iterating.
+	fn offset(source: &str, chars: &Peekable<Chars>) -> usize {
+		source.len() - chars.clone().map(char::len_utf8).sum::<usize>()
+	}
+
+	/// Lexes the two hex digits of a `\xHH` escape.
+	fn hex_byte(chars: &mut Peekable<Chars>) -> Option<u8> {
+		let mut code = String::new();
+		for _ in 0..2 {
+			code.extend(chars.next_if(char::is_ascii_hexdigit));
+		}
+		if code.len() < 2 {
+			return None;
+		}
+		u8::from_str_radix(&code, 16).ok()
+	}
+
+	/// Lexes what follows a backslash, `None` if it's not a valid escape.
+	fn escaped_char(chars: &mut Peekable<Chars>) -> Option<char> {
+		match chars.next()? {
+			'"' => Some('"'),
+			'\'' => Some('\''),
+			'\\' => Some('\\'),
+			'{' => Some('{'),
+			'n' | '\n' => Some('\n'),
+			'r' => Some('\r'),
+			't' => Some('\t'),
+			'x' => Self::hex_byte(chars).filter(u8::is_ascii).map(char::from),
+			'u' => {
+				chars.next_if_eq(&'{')?;
+				let mut code = String::new();
+				while code.len() < 6 {
+					match chars.next_if(char::is_ascii_hexdigit) {
+						Some(digit) => code.push(digit),
+						None => break,
+					}
+				}
+				chars.next_if_eq(&'}')?;
+				char::from_u32(u32::from_str_radix(&code, 16).ok()?)
+			}
+			_ => None,
+		}
+	}
+
+	/// Lexes an escape sequence after its backslash, errors point at the
+	/// backslash.
+	fn lex_escape(chars: &mut Peekable<Chars>, source: &str) -> Result<char, LexerError> {
+		let start = Self::offset(source, chars) - 1;
+		Self::escaped_char(chars).ok_or_else(|| {
+			let end = Self::offset(source, chars);
+			LexerError::InvalidEscape(Box::from(&source[start..end]), Position::of(source, start))
+		})
+	}
+
+	/// Lexes the rest of a char literal such as `'a'` or `'\n'`.
+	fn lex_char(chars: &mut Peekable<Chars>, source: &str) -> Result<Token, LexerError> {
+		let start = Self::offset(source, chars) - 1;
+		let ch = match chars.next() {
+			Some('\\') => Some(Self::lex_escape(chars, source)?),
+			Some(ch) if !matches!(ch, '\'' | '\n') => Some(ch),
+			_ => None,
+		};
+		match (ch, chars.next_if_eq(&'\'')) {
+			(Some(ch), Some(_)) => Ok(Token::LiteralChar(ch)),
+			(ch, _) => {
+				// report the whole of a literal holding more than one char
+				if ch.is_some() {
+					while chars.next_if(|ch| *ch != '\n').is_some_and(|ch| ch != '\'') {}
+				}
+				Err(LexerError::InvalidChar(Box::from(
+					&source[start..Self::offset(source, chars)],
+				)))
+			}
+		}
+	}
+
+	/// Whether a `{` opens an interpolation. Every brace does, except those
+	/// that can't hold a useful expression and are left for `format` and
+	/// regexes: `{}`, counts such as `{3}` and `{2,5}`, and placeholders with a
+	/// spec such as `{:>8}` or `{x:.2}`.
+	fn starts_interpolation(chars: &Peekable<Chars>) -> bool {
+		let mut rest = chars.clone();
+		let mut argument = String::new();
+		while let Some(ch) = rest.next_if(|ch| ch.is_alphanumeric() || *ch == '_') {
+			argument.push(ch);
+		}
+		let count = argument.chars().all(|ch| ch.is_ascii_digit());
+		match rest.next() {
+			Some('}') => !count,
+			Some(',') if count && !argument.is_empty() => {
+				while rest.next_if(char::is_ascii_digit).is_some() {}
+				rest.next() != Some('}')
+			}
+			// `::` as in `{Shape::Empty}` is a path, not a spec
+			Some(':') => rest.peek() == Some(&':'),
+			_ => true,
+		}
+	}
+
+	/// Lexes the rest of a string literal, which may embed expressions in
+	/// braces, as in `"{x + 1}"`. Only braces starting an expression embed one,
+	/// others are plain text, so `"a{3}"` and `"{:>8}"` need no escaping. A
+	/// `multiline` string has already been cut out of the source, so it ends
+	/// with the input instead of a closing quote.
+	fn lex_string(
+		chars: &mut Peekable<Chars>,
+		source: &str,
+		multiline: bool,
+	) -> Result<Token, LexerError> {
+		let mut acc = String::new();
+		let mut parts = Vec::new();
+		let mut closed = multiline;
+		while let Some(ne) = chars.next() {
+			match ne {
+				'\r' => continue,
+				'\n' if !multiline => break,
+				'"' if !multiline => {
+					closed = true;
+					break;
+				}
+				'\\' => acc.push(Self::lex_escape(chars, source)?),
+				'{' | '}' if chars.next_if_eq(&ne).is_some() => acc.push(ne),
+				'{' if Self::starts_interpolation(chars) => {
+					if !acc.is_empty() {
+						parts.push(StringPart::Text(std::mem::take(&mut acc).into()));
+					}
+					let origin = Position::of(source, Self::offset(source, chars));
+					let inner = Self::interpolation_source(chars)?;
+					let tokens = Self::tokenize(&inner).map_err(|err| err.relocated(origin, 0))?;
+					parts.push(StringPart::Code(tokens));
+				}
+				other => acc.push(other),
+			}
+		}
+		if !closed {
+			return Err(LexerError::UnclosedString(acc.into_boxed_str()));
+		}
+
+		if parts.is_empty() {
+			Ok(Token::LiteralString(acc.into_boxed_str()))
+		} else {
+			if !acc.is_empty() {
+				parts.push(StringPart::Text(acc.into_boxed_str()));
+			}
+			Ok(Token::InterpolatedString(parts))
+		}
+	}
+
+	/// Lexes the rest of a byte string literal, where `\xHH` stands for any
+	/// byte and other chars are encoded as UTF-8. Braces are plain text.
+	fn lex_bytes(chars: &mut Peekable<Chars>, source: &str) -> Result<Token, LexerError> {
+		let mut acc = Vec::new();
+		while let Some(ne) = chars.next() {
+			match ne {
+				'"' => return Ok(Token::LiteralBytes(acc)),
+				'\n' => break,
+				'\r' => continue,
+				'\\' if chars.peek() == Some(&'x') => {
+					let start = Self::offset(source, chars) - 1;
+					chars.next();
+					match Self::hex_byte(chars) {
+						Some(byte) => acc.push(byte),
+						None => {
+							let end = Self::offset(source, chars);
+							return Err(LexerError::InvalidEscape(
+								Box::from(&source[start..end]),
+								Position::of(source, start),
+							));
+						}
+					}
+				}
+				'\\' => {
+					let ch = Self::lex_escape(chars, source)?;
+					acc.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
+				}
+				other => acc.extend_from_slice(other.encode_utf8(&mut [0; 4]).as_bytes()),
+			}
+		}
+		Err(LexerError::UnclosedString(Box::from(
+			String::from_utf8_lossy(&acc).as_ref(),
+		)))
+	}
+
+	/// Raw strings, as in `r"C:\path"`, have neither escapes nor interpolation.
+	fn lex_raw_string(chars: &mut Peekable<Chars>) -> Result<Token, LexerError> {
+		let mut acc = String::new();
+		for ne in chars.by_ref() {
+			match ne {
+				'"' => return Ok(Token::LiteralString(acc.into_boxed_str())),
+				'\n' => break,
+				'\r' => continue,
+				other => acc.push(other),
+			}
+		}
+		Err(LexerError::UnclosedString(acc.into_boxed_str()))
+	}
+
+	fn leading_whitespace(line: &str) -> usize {
+		line.chars()
+			.take_while(|ch| matches!(ch, ' ' | '\t'))
+			.count()
+	}
+
+	/// Lexes the rest of a `"""` string. A line break right after the opening
+	/// quotes and a blank last line before the closing ones are dropped, and
+	/// the indentation common to all other lines is stripped. The closing
+	/// quotes count towards it, so they can be used to keep some indentation.
+	fn lex_multiline_string(
+		chars: &mut Peekable<Chars>,
+		source: &str,
+	) -> Result<Token, LexerError> {
+		let mut origin = Position::of(source, Self::offset(source, chars));
+		let mut raw = String::new();
+		let mut escaped = false;
+		loop {
+			match chars.next() {
+				None => return Err(LexerError::UnclosedString(raw.into_boxed_str())),
+				Some('"') if !escaped && chars.clone().take(2).eq(['"', '"']) => {
+					chars.nth(1);
+					break;
+				}
+				// the REPL continues input after a trailing backslash, which
+				// is redundant before a line break inside these strings
+				Some('\\') if !escaped && chars.peek() == Some(&'\n') => continue,
+				Some(ch) => {
+					escaped = ch == '\\' && !escaped;
+					raw.push(ch);
+				}
+			}
+		}
+
+		let mut lines: Vec<&str> = raw.split('\n').collect();
+		let drop_first = lines.len() > 1 && lines[0].trim().is_empty();
+		if drop_first {
+			lines.remove(0);
+		}
+		let closing_indent = match lines.last() {
+			Some(last) if lines.len() > 1 && last.trim().is_empty() => {
+				let indent = Self::leading_whitespace(last);
+				lines.pop();
+				Some(indent)
+			}
+			_ => None,
+		};
+
+		// text on the line of the opening quotes is kept as it is
+		let skip = usize::from(!drop_first);
+		let indent = lines
+			.iter()
+			.skip(skip)
+			.filter(|line| !line.trim().is_empty())
+			.map(|line| Self::leading_whitespace(line))
+			.chain(closing_indent)
+			.min()
+			.unwrap_or(0);
+		let body = lines
+			.iter()
+			.enumerate()
+			.map(|(idx, line)| {
+				if idx < skip {
+					line
+				} else {
+					&line[Self::leading_whitespace(line).min(indent)..]
+				}
+			})
+			.collect::<Box<[_]>>()
+			.join("\n");
+
+		if drop_first {
+			origin = Position {
+				line: origin.line + 1,
+				column: indent + 1,
+			};
+		}
+		Self::lex_string(&mut body.chars().peekable(), &body, true)
+			.map_err(|err| err.relocated(origin, indent))
+	}
+
 	pub fn tokenize(source: &str) -> Result<Vec<Token>, LexerError> {
 		use Token::*;
 
@@ -27,32 +458,60 @@ impl Lexer {
 				'=' => {
 					if chars.next_if(|&ne| ne == '=').is_some() {
 						Equals
+					} else if chars.next_if_eq(&'>').is_some() {
+						FatArrow
 					} else {
 						Assign
 					}
 				}
 				'(' => OpenParen,
 				')' => CloseParen,
+				'[' => OpenBracket,
+				']' => CloseBracket,
+				'{' => OpenBrace,
+				'}' => CloseBrace,
 				'+' => Plus,
 				'-' => Minus,
-				'*' => Star,
-				'/' => Slash,
+				'*' => {
+					if chars.next_if_eq(&'*').is_some() {
+						DoubleStar
+					} else {
+						Star
+					}
+				}
+				'/' => {
+					if chars.next_if_eq(&'/').is_some() {
+						DoubleSlash
+					} else {
+						Slash
+					}
+				}
 				'%' => Percent,
-				'0'..='9' | '.' => {
-					let mut acc = curr.to_string();
-					let mut has_decimal_point = curr == '.';
-
-					while let Some(ne) = chars
-						.next_if(|&ne| ne.is_ascii_digit() || (ne == '.' && !has_decimal_point))
-					{
-						if ne == '.' {
-							has_decimal_point = true;
-						}
-						acc.push(ne);
+				'&' => Ampersand,
+				'|' => Pipe,
+				'^' => Caret,
+				'<' if chars.next_if_eq(&'<').is_some() => DoubleLess,
+				'<' if chars.next_if_eq(&'=').is_some() => LessEqual,
+				'<' => Less,
+				'>' if chars.next_if_eq(&'>').is_some() => DoubleGreater,
+				'>' if chars.next_if_eq(&'=').is_some() => GreaterEqual,
+				'>' => Greater,
+				'!' if chars.next_if_eq(&'=').is_some() => NotEquals,
+				'!' => Bang,
+				'0'..='9' => Self::lex_number(curr, &mut chars)?,
+				'.' if chars.peek().is_some_and(char::is_ascii_digit) => {
+					Self::lex_number(curr, &mut chars)?
+				}
+				'.' if chars.next_if_eq(&'.').is_some() => {
+					if chars.next_if_eq(&'=').is_some() {
+						DotDotEqual
+					} else {
+						DotDot
 					}
-
-					LiteralNumber(acc.into_boxed_str(), has_decimal_point)
 				}
+				'.' => Dot,
+				'r' if chars.next_if_eq(&'"').is_some() => Self::lex_raw_string(&mut chars)?,
+				'b' if chars.next_if_eq(&'"').is_some() => Self::lex_bytes(&mut chars, source)?,
 				'a'..='z' | 'A'..='Z' | '_' => {
 					let mut acc = curr.to_string();
 					while let Some(ne) = chars.next_if(Self::is_identifier_char) {
@@ -65,45 +524,20 @@ impl Lexer {
 						Identifier(acc.into_boxed_str())
 					}
 				}
-				'"' => {
-					let mut acc = String::new();
-					let mut escaped = false;
-					let mut closed = false;
-					for ne in chars.by_ref() {
-						if ne == '\r' {
-							continue;
-						}
-
-						if escaped {
-							match ne {
-								'"' => acc.push('"'),
-								'\\' => acc.push('\\'),
-								'n' | '\n' => acc.push('\n'),
-								'r' => acc.push('\r'),
-								't' => acc.push('\t'),
-								other => return Err(LexerError::UnsupportedEscape(other)),
-							}
-							escaped = false;
-							continue;
-						}
-
-						match ne {
-							'\n' => return Err(LexerError::UnclosedString(acc.into_boxed_str())),
-							'"' => {
-								closed = true;
-								break;
-							}
-							'\\' => escaped = true,
-							other => acc.push(other),
-						}
-					}
-					if !closed {
-						return Err(LexerError::UnclosedString(acc.into_boxed_str()));
+				'"' if chars.next_if_eq(&'"').is_some() => {
+					if chars.next_if_eq(&'"').is_some() {
+						Self::lex_multiline_string(&mut chars, source)?
+					} else {
+						LiteralString(Box::from(""))
 					}
-					LiteralString(acc.into_boxed_str())
 				}
+				'"' => Self::lex_string(&mut chars, source, false)?,
+				'\'' => Self::lex_char(&mut chars, source)?,
 				' ' | '\t' | '\n' => continue,
 				';' => Semicolon,
+				',' => Comma,
+				':' if chars.next_if_eq(&':').is_some() => DoubleColon,
+				':' => Colon,
 				'~' => Mutable,
 				other => return Err(LexerError::UnexpectedChar(other)),
 			};
@@ -112,3 +546,239 @@ impl Lexer {
 		Ok(tokens)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		error::LexerError,
+		token::{NumberKind, StringPart, Token},
+		Lexer,
+	};
+
+	fn single(source: &str) -> Token {
+		match Lexer::tokenize(source).unwrap().as_slice() {
+			[token] => token.to_owned(),
+			tokens => panic!("expected one token for {source}, got {tokens:?}"),
+		}
+	}
+
+	fn error(source: &str) -> LexerError {
+		Lexer::tokenize(source).unwrap_err()
+	}
+
+	fn string(source: &str) -> String {
+		match single(source) {
+			Token::LiteralString(text) => text.into(),
+			other => panic!("expected a plain string for {source}, got {other:?}"),
+		}
+	}
+
+	fn number(source: &str) -> (String, NumberKind) {
+		match single(source) {
+			Token::LiteralNumber(digits, kind) => (digits.into(), kind),
+			other => panic!("expected a number for {source}, got {other:?}"),
+		}
+	}
+
+	#[test]
+	fn number_literals() {
+		assert!(matches!(number("1_000"), (d, NumberKind::Int(10)) if d == "1000"));
+		assert!(matches!(number("0xff"), (d, NumberKind::Int(16)) if d == "ff"));
+		assert!(matches!(number("0b1010"), (d, NumberKind::Int(2)) if d == "1010"));
+		assert!(matches!(number("0o17"), (d, NumberKind::Int(8)) if d == "17"));
+		assert!(matches!(number("2.5e-3"), (d, NumberKind::Float) if d == "2.5e-3"));
+		assert!(matches!(number("1e3"), (_, NumberKind::Float)));
+		assert!(matches!(number("3r"), (_, NumberKind::Rational)));
+		assert!(matches!(number("19.99d"), (_, NumberKind::Decimal)));
+	}
+
+	#[test]
+	fn malformed_number_literals() {
+		use LexerError::*;
+
+		assert!(matches!(error("0x"), MissingDigits(_)));
+		assert!(matches!(error("0b102"), InvalidDigit('2', 2)));
+		assert!(matches!(error("1__000"), MisplacedSeparator(_)));
+		assert!(matches!(error("0x_ff"), MisplacedSeparator(_)));
+		assert!(matches!(error("1_"), MisplacedSeparator(_)));
+		assert!(matches!(error("1e"), MissingExponent(_)));
+	}
+
+	#[test]
+	fn braces_interpolate_any_expression() {
+		let Token::InterpolatedString(parts) = single(r#""x = {x + 1}!""#) else {
+			panic!("expected an interpolated string");
+		};
+		assert!(matches!(
+			parts.as_slice(),
+			[StringPart::Text(before), StringPart::Code(code), StringPart::Text(after)]
+				if &**before == "x = " && code.len() == 3 && &**after == "!"
+		));
+		assert!(matches!(
+			single(r#""{ (1) }""#),
+			Token::InterpolatedString(_)
+		));
+		assert!(matches!(single(r#""{_x}""#), Token::InterpolatedString(_)));
+		for source in [
+			r#""{1 + 2}""#,
+			r#""{-x}""#,
+			r#""{[1]}""#,
+			r#""{1/2r}""#,
+			r#""{Shape::Empty}""#,
+		] {
+			assert!(
+				matches!(single(source), Token::InterpolatedString(_)),
+				"{source}"
+			);
+		}
+	}
+
+	#[test]
+	fn doubled_braces_are_literal() {
+		assert_eq!(string(r#""{{x}}""#), "{x}");
+		assert_eq!(string(r#""a}}b{{""#), "a}b{");
+		assert_eq!(string(r#""a}b""#), "a}b");
+		assert_eq!(string(r#""\{x}""#), "{x}");
+	}
+
+	#[test]
+	fn counts_and_placeholders_are_plain_text() {
+		assert_eq!(string(r#""a{3}""#), "a{3}");
+		assert_eq!(string(r#""(a{1000}){1,3}{2,}""#), "(a{1000}){1,3}{2,}");
+		assert_eq!(string(r#""{:>8.2} | {} {0}""#), "{:>8.2} | {} {0}");
+		assert_eq!(string(r#""{x:>8.2} {0:?}""#), "{x:>8.2} {0:?}");
+	}
+
+	#[test]
+	fn interpolation_errors() {
+		assert!(matches!(
+			error(r#""{x""#),
+			LexerError::UnclosedInterpolation(_)
+		));
+		assert!(matches!(
+			error(r#""{x"#),
+			LexerError::UnclosedInterpolation(_)
+		));
+		assert!(matches!(
+			error(r#""{x $}""#),
+			LexerError::UnexpectedChar('$')
+		));
+		assert!(matches!(
+			error(r#""}{""#),
+			LexerError::UnclosedInterpolation(_)
+		));
+	}
+
+	#[test]
+	fn escapes() {
+		assert_eq!(string(r#""a\tb\n""#), "a\tb\n");
+		assert_eq!(string(r#""\"\\\'""#), "\"\\'");
+		assert_eq!(string(r#""\x41\u{e9}\u{1F600}""#), "A\u{e9}\u{1F600}");
+	}
+
+	#[test]
+	fn invalid_escapes() {
+		for source in [
+			r#""\q""#,
+			r#""\xff""#,
+			r#""\x4""#,
+			r#""\u{110000}""#,
+			r#""\u{}""#,
+			r#""\u41""#,
+		] {
+			assert!(
+				matches!(error(source), LexerError::InvalidEscape(..)),
+				"{source}"
+			);
+		}
+	}
+
+	#[test]
+	fn invalid_escapes_report_their_position() {
+		let LexerError::InvalidEscape(escape, position) = error("1 +\n  \"ab\\q\"") else {
+			panic!("expected an invalid escape");
+		};
+		assert_eq!(&*escape, "\\q");
+		assert_eq!((position.line, position.column), (2, 6));
+
+		let LexerError::InvalidEscape(_, position) = error(r#""{"\q"}""#) else {
+			panic!("expected an invalid escape");
+		};
+		assert_eq!((position.line, position.column), (1, 4));
+	}
+
+	#[test]
+	fn raw_strings_keep_backslashes_and_braces() {
+		assert_eq!(string(r#"r"C:\path\{x}""#), "C:\\path\\{x}");
+	}
+
+	#[test]
+	fn multiline_strings_strip_common_indentation() {
+		let source = "\"\"\"\n    one\n      two\n    \"\"\"";
+		assert_eq!(string(source), "one\n  two");
+		let source = "\"\"\"\n    one\n  \"\"\"";
+		assert_eq!(string(source), "  one");
+		assert!(matches!(error("\"\"\"abc"), LexerError::UnclosedString(_)));
+	}
+
+	#[test]
+	fn char_literals() {
+		assert!(matches!(single("'a'"), Token::LiteralChar('a')));
+		assert!(matches!(single("'\u{e9}'"), Token::LiteralChar('\u{e9}')));
+		assert!(matches!(single(r"'\n'"), Token::LiteralChar('\n')));
+		assert!(matches!(single(r"'\''"), Token::LiteralChar('\'')));
+		assert!(matches!(
+			single(r"'\u{1F600}'"),
+			Token::LiteralChar('\u{1F600}')
+		));
+	}
+
+	#[test]
+	fn invalid_char_literals() {
+		for (source, literal) in [
+			("''", "''"),
+			("'ab'", "'ab'"),
+			("'a", "'a"),
+			("'a\nb'", "'a"),
+		] {
+			let err = error(source);
+			assert!(
+				matches!(&err, LexerError::InvalidChar(found) if &**found == literal),
+				"{source}: {err:?}"
+			);
+		}
+		assert!(matches!(error(r"'\q'"), LexerError::InvalidEscape(..)));
+	}
+
+	#[test]
+	fn byte_literals() {
+		let Token::LiteralBytes(bytes) = single(r#"b"a\x00\xff\n{x}é""#) else {
+			panic!("expected bytes");
+		};
+		assert_eq!(bytes, b"a\x00\xff\n{x}\xc3\xa9");
+	}
+
+	#[test]
+	fn invalid_byte_literals() {
+		let LexerError::InvalidEscape(escape, _) = error(r#"b"\xg0""#) else {
+			panic!("expected an invalid escape");
+		};
+		assert_eq!(&*escape, "\\x");
+		assert!(matches!(error(r#"b"\q""#), LexerError::InvalidEscape(..)));
+		assert!(matches!(error(r#"b"abc"#), LexerError::UnclosedString(_)));
+		assert!(matches!(error("b\"a\nb\""), LexerError::UnclosedString(_)));
+	}
+
+	#[test]
+	fn ranges_are_not_floats() {
+		let tokens = Lexer::tokenize("1..5").unwrap();
+		assert!(matches!(
+			tokens.as_slice(),
+			[
+				Token::LiteralNumber(..),
+				Token::DotDot,
+				Token::LiteralNumber(..)
+			]
+		));
+	}
+}
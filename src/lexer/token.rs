@@ -1,22 +1,75 @@
 use std::fmt::Display;
 
+use crate::helpers::{escape_bytes, escape_string_text};
+
+#[derive(Debug, Clone, Copy)]
+pub enum NumberKind {
+	Int(u32),
+	Float,
+	Rational,
+	Decimal,
+}
+
+/// A piece of an interpolated string literal: plain text or the tokens of an
+/// embedded expression.
+#[derive(Debug, Clone)]
+pub enum StringPart {
+	Text(Box<str>),
+	Code(Vec<Token>),
+}
+
 #[derive(Debug, Clone)]
 pub enum Token {
-	LiteralNumber(Box<str>, bool),
+	LiteralNumber(Box<str>, NumberKind),
 	LiteralString(Box<str>),
+	LiteralChar(char),
+	LiteralBytes(Vec<u8>),
+	InterpolatedString(Vec<StringPart>),
 	Identifier(Box<str>),
 	Let,
+	Struct,
+	Enum,
+	Match,
+	If,
+	For,
+	In,
+	Break,
+	Continue,
 	Assign,
+	FatArrow,
 	OpenParen,
 	CloseParen,
+	OpenBracket,
+	CloseBracket,
+	OpenBrace,
+	CloseBrace,
 	Plus,
 	Minus,
 	Star,
+	DoubleStar,
 	Slash,
+	DoubleSlash,
 	Percent,
+	Ampersand,
+	Pipe,
+	Caret,
+	DoubleLess,
+	DoubleGreater,
+	Bang,
 	Equals,
+	NotEquals,
+	Less,
+	LessEqual,
+	Greater,
+	GreaterEqual,
 	Unit,
 	Semicolon,
+	Comma,
+	Colon,
+	DoubleColon,
+	Dot,
+	DotDot,
+	DotDotEqual,
 	Mutable,
 }
 
@@ -24,21 +77,78 @@ impl Display for Token {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		use Token::*;
 		match self {
+			LiteralNumber(num, NumberKind::Int(16)) => write!(f, "0x{num}"),
+			LiteralNumber(num, NumberKind::Int(8)) => write!(f, "0o{num}"),
+			LiteralNumber(num, NumberKind::Int(2)) => write!(f, "0b{num}"),
+			LiteralNumber(num, NumberKind::Rational) => write!(f, "{num}r"),
+			LiteralNumber(num, NumberKind::Decimal) => write!(f, "{num}d"),
 			LiteralNumber(num, _) => f.write_str(num),
 			LiteralString(st) => write!(f, "{st:?}"),
+			LiteralChar(ch) => write!(f, "{ch:?}"),
+			LiteralBytes(bytes) => write!(f, "b\"{}\"", escape_bytes(bytes)),
+			InterpolatedString(parts) => {
+				f.write_str("\"")?;
+				for part in parts {
+					match part {
+						StringPart::Text(text) => f.write_str(&escape_string_text(text))?,
+						StringPart::Code(tokens) => write!(
+							f,
+							"{{{}}}",
+							tokens
+								.iter()
+								.map(|token| token.to_string())
+								.collect::<Box<[_]>>()
+								.join(" ")
+						)?,
+					}
+				}
+				f.write_str("\"")
+			}
 			Identifier(ident) => f.write_str(ident),
 			Let => f.write_str("let"),
+			Struct => f.write_str("struct"),
+			Enum => f.write_str("enum"),
+			Match => f.write_str("match"),
+			If => f.write_str("if"),
+			For => f.write_str("for"),
+			In => f.write_str("in"),
+			Break => f.write_str("break"),
+			Continue => f.write_str("continue"),
 			Assign => f.write_str("="),
+			FatArrow => f.write_str("=>"),
 			OpenParen => f.write_str("("),
 			CloseParen => f.write_str(")"),
+			OpenBracket => f.write_str("["),
+			CloseBracket => f.write_str("]"),
+			OpenBrace => f.write_str("{"),
+			CloseBrace => f.write_str("}"),
 			Plus => f.write_str("+"),
 			Minus => f.write_str("-"),
 			Star => f.write_str("*"),
+			DoubleStar => f.write_str("**"),
 			Slash => f.write_str("/"),
+			DoubleSlash => f.write_str("//"),
 			Percent => f.write_str("%"),
+			Ampersand => f.write_str("&"),
+			Pipe => f.write_str("|"),
+			Caret => f.write_str("^"),
+			DoubleLess => f.write_str("<<"),
+			DoubleGreater => f.write_str(">>"),
+			Bang => f.write_str("!"),
 			Equals => f.write_str("=="),
+			NotEquals => f.write_str("!="),
+			Less => f.write_str("<"),
+			LessEqual => f.write_str("<="),
+			Greater => f.write_str(">"),
+			GreaterEqual => f.write_str(">="),
 			Unit => f.write_str("_"),
 			Semicolon => f.write_str(";"),
+			Comma => f.write_str(","),
+			Colon => f.write_str(":"),
+			DoubleColon => f.write_str("::"),
+			Dot => f.write_str("."),
+			DotDot => f.write_str(".."),
+			DotDotEqual => f.write_str("..="),
 			Mutable => f.write_str("~"),
 		}
 	}
@@ -4,12 +4,14 @@ use environment::Env;
 use parser::Parser;
 use runtime::Runtime;
 
+mod builtins;
 mod environment;
 mod expression;
 mod helpers;
 mod lexer;
 mod numeric;
 mod parser;
+mod regex;
 mod runtime;
 
 fn main() {
@@ -50,7 +52,11 @@ fn main() {
 
 		input.pop();
 
-		match parser.produce_ast(&input, print_debug) {
+		let ast = parser.produce_ast(&input, print_debug);
+		for warning in parser.take_warnings() {
+			eprintln!("Warning: {warning}");
+		}
+		match ast {
 			Ok(ast) => {
 				let res = runtime.evaluate(ast);
 				match res {
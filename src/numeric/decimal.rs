@@ -0,0 +1,408 @@
+use std::{
+	cmp::Ordering,
+	fmt::Display,
+	ops::{Add, Mul, Neg, Sub},
+	str::FromStr,
+};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{Pow, Signed, Zero};
+
+/// Number of fractional digits `/` keeps when the quotient of two decimals
+/// doesn't terminate earlier.
+pub const DIVISION_SCALE: u32 = 28;
+
+/// Largest number of fractional digits that can be asked for when rounding.
+pub const MAX_PLACES: u32 = 10_000;
+
+/// Largest scale a decimal can have, which bounds the powers of ten needed to
+/// align two decimals.
+pub const MAX_SCALE: u32 = 1 << 18;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundingMode {
+	Up,
+	Down,
+	Ceiling,
+	Floor,
+	HalfUp,
+	HalfDown,
+	HalfEven,
+}
+
+impl FromStr for RoundingMode {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		use RoundingMode::*;
+
+		match s {
+			"up" => Ok(Up),
+			"down" => Ok(Down),
+			"ceiling" => Ok(Ceiling),
+			"floor" => Ok(Floor),
+			"half_up" => Ok(HalfUp),
+			"half_down" => Ok(HalfDown),
+			"half_even" => Ok(HalfEven),
+			_ => Err(()),
+		}
+	}
+}
+
+impl RoundingMode {
+	/// Divides `numer` by `denom`, rounding the quotient to an integer.
+	fn divide(self, numer: &BigInt, denom: &BigInt) -> BigInt {
+		use RoundingMode::*;
+
+		let (quotient, remainder) = numer.div_rem(denom);
+		if remainder.is_zero() {
+			return quotient;
+		}
+
+		let positive = numer.is_negative() == denom.is_negative();
+		let half = (remainder.abs() * 2u8).cmp(&denom.abs());
+		let away_from_zero = match self {
+			Up => true,
+			Down => false,
+			Ceiling => positive,
+			Floor => !positive,
+			HalfUp => half != Ordering::Less,
+			HalfDown => half == Ordering::Greater,
+			HalfEven => half == Ordering::Greater || (half == Ordering::Equal && quotient.is_odd()),
+		};
+
+		match (away_from_zero, positive) {
+			(false, _) => quotient,
+			(true, true) => quotient + 1u8,
+			(true, false) => quotient - 1u8,
+		}
+	}
+}
+
+/// A base 10 fixed-point number with the value `mantissa * 10^-scale`.
+/// Results of `+`, `-` and `*` are exact and keep the scale of their
+/// operands, so `1.10 + 2.20` is `3.30`. The scale never exceeds `MAX_SCALE`.
+#[derive(Debug, Clone)]
+pub struct Decimal {
+	mantissa: BigInt,
+	scale: u32,
+}
+
+fn ten_pow(exponent: u32) -> BigInt {
+	BigInt::from(10).pow(exponent)
+}
+
+impl Decimal {
+	pub fn new(mantissa: BigInt, scale: u32) -> Self {
+		Self { mantissa, scale }
+	}
+
+	/// Parses a literal such as `19.99` or `2.5e-3`.
+	pub fn parse(src: &str) -> Option<Self> {
+		let (mantissa, exponent) = src.split_once('e').unwrap_or((src, "0"));
+		let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+		let mantissa: BigInt = format!("{int_part}{frac_part}").parse().ok()?;
+		let scale = i32::try_from(frac_part.len()).ok()? - i32::from(exponent.parse::<i16>().ok()?);
+		if scale > MAX_SCALE as i32 {
+			return None;
+		}
+		Some(if scale < 0 {
+			Self {
+				mantissa: mantissa * ten_pow(scale.unsigned_abs()),
+				scale: 0,
+			}
+		} else {
+			Self {
+				mantissa,
+				scale: scale.unsigned_abs(),
+			}
+		})
+	}
+
+	/// The mantissa at a scale at least as large as the current one.
+	fn mantissa_at(&self, scale: u32) -> BigInt {
+		&self.mantissa * ten_pow(scale - self.scale)
+	}
+
+	fn align(&self, rhs: &Decimal) -> (BigInt, BigInt, u32) {
+		let scale = self.scale.max(rhs.scale);
+		(self.mantissa_at(scale), rhs.mantissa_at(scale), scale)
+	}
+
+	pub fn scale(&self) -> u32 {
+		self.scale
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.mantissa.is_zero()
+	}
+
+	pub fn to_rational(&self) -> BigRational {
+		BigRational::new(self.mantissa.to_owned(), ten_pow(self.scale))
+	}
+
+	pub fn to_f64(&self) -> f64 {
+		self.to_string().parse().unwrap_or(f64::NAN)
+	}
+
+	/// Rounds to `scale` fractional digits.
+	pub fn round(&self, scale: u32, mode: RoundingMode) -> Self {
+		let mantissa = if scale >= self.scale {
+			self.mantissa_at(scale)
+		} else {
+			mode.divide(&self.mantissa, &ten_pow(self.scale - scale))
+		};
+		Self { mantissa, scale }
+	}
+
+	pub fn to_integer(&self, mode: RoundingMode) -> BigInt {
+		self.round(0, mode).mantissa
+	}
+
+	/// Divides to `scale` fractional digits, `None` when dividing by zero.
+	pub fn div(&self, rhs: &Decimal, scale: u32, mode: RoundingMode) -> Option<Self> {
+		if rhs.is_zero() {
+			return None;
+		}
+		let numer = &self.mantissa * ten_pow(rhs.scale + scale);
+		let denom = &rhs.mantissa * ten_pow(self.scale);
+		Some(Self {
+			mantissa: mode.divide(&numer, &denom),
+			scale,
+		})
+	}
+
+	/// Removes trailing fractional zeros without going below `min_scale`.
+	pub fn trim(mut self, min_scale: u32) -> Self {
+		let ten = BigInt::from(10);
+		while self.scale > min_scale && (&self.mantissa % &ten).is_zero() {
+			self.mantissa /= &ten;
+			self.scale -= 1;
+		}
+		self
+	}
+
+	/// Flooring division and its remainder, which takes the divisor's sign.
+	/// `None` when dividing by zero.
+	pub fn div_mod_floor(&self, rhs: &Decimal) -> Option<(BigInt, Self)> {
+		if rhs.is_zero() {
+			return None;
+		}
+		let (l, r, scale) = self.align(rhs);
+		let (quotient, remainder) = l.div_mod_floor(&r);
+		Some((
+			quotient,
+			Self {
+				mantissa: remainder,
+				scale,
+			},
+		))
+	}
+
+	/// Truncating remainder, `None` when dividing by zero.
+	pub fn rem(&self, rhs: &Decimal) -> Option<Self> {
+		if rhs.is_zero() {
+			return None;
+		}
+		let (l, r, scale) = self.align(rhs);
+		Some(Self {
+			mantissa: l % r,
+			scale,
+		})
+	}
+
+	/// `None` if the scale of the power exceeds `MAX_SCALE`.
+	pub fn pow(&self, exponent: u32) -> Option<Self> {
+		Some(Self {
+			scale: self
+				.scale
+				.checked_mul(exponent)
+				.filter(|&scale| scale <= MAX_SCALE)?,
+			mantissa: Pow::pow(&self.mantissa, exponent),
+		})
+	}
+
+	/// Roughly the number of bits needed for the mantissa and for the power
+	/// of ten it is divided by.
+	pub fn bits(&self) -> u64 {
+		self.mantissa.bits().max(u64::from(self.scale) * 4)
+	}
+
+	pub fn abs(&self) -> Self {
+		Self {
+			mantissa: self.mantissa.abs(),
+			scale: self.scale,
+		}
+	}
+}
+
+impl From<BigInt> for Decimal {
+	fn from(value: BigInt) -> Self {
+		Self {
+			mantissa: value,
+			scale: 0,
+		}
+	}
+}
+
+impl PartialEq for Decimal {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Decimal {
+	fn cmp(&self, other: &Self) -> Ordering {
+		let (l, r, _) = self.align(other);
+		l.cmp(&r)
+	}
+}
+
+impl Add<Decimal> for Decimal {
+	type Output = Self;
+	fn add(self, rhs: Decimal) -> Self::Output {
+		let (l, r, scale) = self.align(&rhs);
+		Self {
+			mantissa: l + r,
+			scale,
+		}
+	}
+}
+
+impl Sub<Decimal> for Decimal {
+	type Output = Self;
+	fn sub(self, rhs: Decimal) -> Self::Output {
+		let (l, r, scale) = self.align(&rhs);
+		Self {
+			mantissa: l - r,
+			scale,
+		}
+	}
+}
+
+/// `None` if the scale of the product exceeds `MAX_SCALE`.
+impl Mul<Decimal> for Decimal {
+	type Output = Option<Self>;
+	fn mul(self, rhs: Decimal) -> Self::Output {
+		Some(Self {
+			scale: self
+				.scale
+				.checked_add(rhs.scale)
+				.filter(|&scale| scale <= MAX_SCALE)?,
+			mantissa: self.mantissa * rhs.mantissa,
+		})
+	}
+}
+
+impl Neg for Decimal {
+	type Output = Self;
+	fn neg(self) -> Self::Output {
+		Self {
+			mantissa: -self.mantissa,
+			scale: self.scale,
+		}
+	}
+}
+
+impl Display for Decimal {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let sign = if self.mantissa.is_negative() { "-" } else { "" };
+		let digits = self.mantissa.abs().to_string();
+		let scale = self.scale as usize;
+		if scale == 0 {
+			return write!(f, "{sign}{digits}");
+		}
+
+		// padded by hand, since a formatting width can't exceed `u16::MAX`
+		let padding = "0".repeat((scale + 1).saturating_sub(digits.len()));
+		let digits = padding + &digits;
+		let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+		write!(f, "{sign}{int_part}.{frac_part}")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use num_bigint::BigInt;
+
+	use super::{Decimal, RoundingMode::*, MAX_SCALE};
+
+	fn decimal(src: &str) -> Decimal {
+		Decimal::parse(src).unwrap()
+	}
+
+	#[test]
+	fn rounding_modes() {
+		let modes = [Up, Down, Ceiling, Floor, HalfUp, HalfDown, HalfEven];
+		let cases = [
+			("2.5", ["3", "2", "3", "2", "3", "2", "2"]),
+			("-2.5", ["-3", "-2", "-2", "-3", "-3", "-2", "-2"]),
+			("3.5", ["4", "3", "4", "3", "4", "3", "4"]),
+			("2.6", ["3", "2", "3", "2", "3", "3", "3"]),
+			("-2.4", ["-3", "-2", "-2", "-3", "-2", "-2", "-2"]),
+			("7", ["7", "7", "7", "7", "7", "7", "7"]),
+		];
+		for (src, expected) in cases {
+			for (mode, expected) in modes.into_iter().zip(expected) {
+				let rounded = decimal(src).round(0, mode);
+				assert_eq!(rounded.to_string(), expected, "{src} rounded {mode:?}");
+			}
+		}
+	}
+
+	#[test]
+	fn rounding_keeps_the_requested_scale() {
+		assert_eq!(decimal("1.25").round(1, HalfEven).to_string(), "1.2");
+		assert_eq!(decimal("1.35").round(1, HalfEven).to_string(), "1.4");
+		assert_eq!(decimal("1.5").round(3, Down).to_string(), "1.500");
+		assert_eq!(decimal("-0.05").round(1, HalfUp).to_string(), "-0.1");
+	}
+
+	#[test]
+	fn arithmetic_keeps_the_scale_of_the_operands() {
+		assert_eq!((decimal("1.10") + decimal("2.205")).to_string(), "3.305");
+		assert_eq!((decimal("1.10") - decimal("2.20")).to_string(), "-1.10");
+		assert_eq!(
+			(decimal("1.5") * decimal("1.5")).unwrap().to_string(),
+			"2.25"
+		);
+		assert_eq!(decimal("2.5e-3").to_string(), "0.0025");
+		assert_eq!(decimal("1.5e2").to_string(), "150");
+	}
+
+	#[test]
+	fn division_rounds_to_the_given_scale() {
+		let third = decimal("1").div(&decimal("3"), 4, HalfUp).unwrap();
+		assert_eq!(third.to_string(), "0.3333");
+		let two_thirds = decimal("2").div(&decimal("3"), 2, Down).unwrap();
+		assert_eq!(two_thirds.to_string(), "0.66");
+		assert!(decimal("1").div(&decimal("0.00"), 2, Up).is_none());
+	}
+
+	#[test]
+	fn pow_checks_the_scale() {
+		assert_eq!(decimal("0.5").pow(3).unwrap().to_string(), "0.125");
+		assert!(decimal("0.05").pow(u32::MAX).is_none());
+	}
+
+	#[test]
+	fn scales_are_capped() {
+		let tiny = decimal("1e-32767");
+		assert_eq!(tiny.scale(), 32767);
+		let square = (tiny.clone() * tiny).unwrap();
+		assert_eq!(square.to_string().len(), 2 + 65534);
+		let largest = Decimal::new(BigInt::from(1), MAX_SCALE);
+		assert!((largest.clone() * decimal("0.1")).is_none());
+		assert_eq!((largest + decimal("1")).scale(), MAX_SCALE);
+		assert!(Decimal::parse(&format!("0.{}", "0".repeat(MAX_SCALE as usize + 1))).is_none());
+	}
+}
@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use super::{decimal::MAX_SCALE, unit::Dimension, Numeric};
+
+#[derive(Debug, Clone)]
+pub enum NumericError {
+	DivisionByZero,
+	ExponentTooLarge(Numeric),
+	ShiftOutOfRange(Numeric),
+	DimensionMismatch(Dimension, Dimension),
+	DimensionOverflow(Dimension, Dimension),
+	FractionalDimension(Dimension),
+	ScaleTooLarge,
+}
+
+impl Display for NumericError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		use NumericError::*;
+
+		match self {
+			DivisionByZero => f.write_str("Division by zero"),
+			ExponentTooLarge(exponent) => write!(f, "Exponent is too large: {exponent}"),
+			ShiftOutOfRange(amount) => write!(f, "Shift amount is out of range: {amount}"),
+			DimensionMismatch(left, right) => write!(f, "Units don't match: {left} and {right}"),
+			DimensionOverflow(left, right) => {
+				write!(f, "Unit exponents are out of range: {left} and {right}")
+			}
+			FractionalDimension(dimension) => {
+				write!(f, "Unit {dimension} can't be raised to a fractional power")
+			}
+			ScaleTooLarge => write!(f, "Decimal has more than {MAX_SCALE} fractional digits"),
+		}
+	}
+}
@@ -0,0 +1,972 @@
+pub mod decimal;
+pub mod error;
+pub mod unit;
+
+use std::{
+	cmp::Ordering,
+	fmt::Display,
+	ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub},
+};
+
+use decimal::RoundingMode;
+use error::NumericError;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, Pow, Signed, ToPrimitive, Zero};
+use unit::Dimension;
+
+/// A number value. Integers are stored as `Int` while they fit in an `i128`
+/// and are promoted to `Big` when an operation overflows; every operation
+/// demotes a `Big` result back to `Int` once it fits again.
+///
+/// `Rational` is always kept in lowest terms and `Decimal` is a base 10
+/// fixed-point number. When the operands of a binary operation have different
+/// variants, the result is
+/// - a `Float` if either of them is a `Float`,
+/// - otherwise an exact `Rational` if either of them is a `Rational`,
+/// - otherwise an exact `Decimal` if either of them is a `Decimal`,
+/// - otherwise an integer.
+///
+/// A `Quantity` is a plain number with a unit, stored in SI base units. `+`,
+/// `-`, `%` and comparisons require both operands to have the same dimension,
+/// while `*` and `/` combine them. A plain number is dimensionless.
+///
+/// Equality and `compare` go by value across variants, so `1 == 1.0` and
+/// `0.5 == 1/2r`; floats are compared exactly rather than after rounding the
+/// other operand to a float. NaN is unordered: it is unequal to everything,
+/// itself included. `total_cmp` provides an order that includes NaN.
+#[derive(Debug, Clone)]
+pub enum Numeric {
+	Int(i128),
+	Big(BigInt),
+	Float(f64),
+	Rational(Box<BigRational>),
+	Decimal(decimal::Decimal),
+	Quantity(Box<Numeric>, Dimension),
+}
+
+/// Both operands of a binary operation, converted to a common representation.
+enum Coerced {
+	Ints(i128, i128),
+	Bigs(BigInt, BigInt),
+	Floats(f64, f64),
+	Rationals(BigRational, BigRational),
+	Decimals(decimal::Decimal, decimal::Decimal),
+}
+
+impl Numeric {
+	fn big_to_f64(big: &BigInt) -> f64 {
+		big.to_f64().unwrap_or(f64::NAN)
+	}
+
+	fn rational_to_f64(rational: &BigRational) -> f64 {
+		rational.to_f64().unwrap_or(f64::NAN)
+	}
+
+	pub fn to_f64(&self) -> f64 {
+		use Numeric::*;
+
+		match self {
+			Int(i) => *i as f64,
+			Big(b) => Self::big_to_f64(b),
+			Float(f) => *f,
+			Rational(r) => Self::rational_to_f64(r),
+			Decimal(d) => d.to_f64(),
+			Quantity(q, _) => q.to_f64(),
+		}
+	}
+
+	/// Exact for every variant but the non-finite floats, which become zero.
+	fn into_rational(self) -> BigRational {
+		use Numeric::*;
+
+		match self {
+			Int(i) => BigRational::from(BigInt::from(i)),
+			Big(b) => BigRational::from(b),
+			Float(f) => BigRational::from_float(f).unwrap_or_default(),
+			Rational(r) => *r,
+			Decimal(d) => d.to_rational(),
+			Quantity(q, _) => q.into_rational(),
+		}
+	}
+
+	/// Parses a literal such as `7`, `1.25` or `2.5e-3` into an exact `Rational`.
+	pub fn parse_rational(src: &str) -> Option<Self> {
+		let (mantissa, exponent) = src.split_once('e').unwrap_or((src, "0"));
+		let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+		let numer: BigInt = format!("{int_part}{frac_part}").parse().ok()?;
+		let exponent =
+			i32::from(exponent.parse::<i16>().ok()?) - i32::try_from(frac_part.len()).ok()?;
+		let scale = BigInt::from(10).pow(exponent.unsigned_abs());
+		let rational = if exponent < 0 {
+			BigRational::new(numer, scale)
+		} else {
+			BigRational::from(numer * scale)
+		};
+		Some(Numeric::Rational(Box::new(rational)))
+	}
+
+	/// Parses a literal such as `19.99` into a `Decimal`.
+	pub fn parse_decimal(src: &str) -> Option<Self> {
+		decimal::Decimal::parse(src).map(Numeric::Decimal)
+	}
+
+	pub fn is_integer(&self) -> bool {
+		matches!(self, Numeric::Int(_) | Numeric::Big(_))
+	}
+
+	/// `None` for floats and rationals, which may not have an exact decimal
+	/// representation.
+	pub fn to_decimal(&self) -> Option<decimal::Decimal> {
+		use Numeric::*;
+		match self {
+			Int(i) => Some(BigInt::from(*i).into()),
+			Big(b) => Some(b.to_owned().into()),
+			Decimal(d) => Some(d.to_owned()),
+			Float(_) | Rational(_) | Quantity(..) => None,
+		}
+	}
+
+	fn into_big_integer(self) -> Option<BigInt> {
+		use Numeric::*;
+		match self {
+			Int(i) => Some(BigInt::from(i)),
+			Big(b) => Some(b),
+			Float(_) | Rational(_) | Decimal(_) | Quantity(..) => None,
+		}
+	}
+
+	fn shift_amount(self) -> Option<Result<u32, NumericError>> {
+		use Numeric::*;
+		match &self {
+			Int(i @ 0..=MAX_SHIFT) => Some(Ok(*i as u32)),
+			Int(_) | Big(_) => Some(Err(NumericError::ShiftOutOfRange(self))),
+			Float(_) | Rational(_) | Decimal(_) | Quantity(..) => None,
+		}
+	}
+
+	pub fn is_quantity(&self) -> bool {
+		matches!(self, Numeric::Quantity(..))
+	}
+
+	/// Attaches a dimension to a plain number, the result stays a plain
+	/// number when the dimensions cancel out.
+	pub fn with_dimension(self, dimension: Dimension) -> Self {
+		debug_assert!(!self.is_quantity());
+		if dimension.is_none() {
+			self
+		} else {
+			Numeric::Quantity(Box::new(self), dimension)
+		}
+	}
+
+	fn into_parts(self) -> (Numeric, Dimension) {
+		match self {
+			Numeric::Quantity(magnitude, dimension) => (*magnitude, dimension),
+			plain => (plain, Dimension::NONE),
+		}
+	}
+
+	/// Strips the dimensions of both operands, which have to match.
+	fn split_matching(self, rhs: Numeric) -> Result<(Numeric, Numeric, Dimension), NumericError> {
+		let ((l, d1), (r, d2)) = (self.into_parts(), rhs.into_parts());
+		if d1 == d2 {
+			Ok((l, r, d1))
+		} else {
+			Err(NumericError::DimensionMismatch(d1, d2))
+		}
+	}
+
+	fn coerce(self, rhs: Numeric) -> Coerced {
+		use Coerced::*;
+		use Numeric::*;
+
+		match (self, rhs) {
+			(Quantity(..), _) | (_, Quantity(..)) => {
+				unreachable!("quantities are split before coercion")
+			}
+			(Int(i1), Int(i2)) => Ints(i1, i2),
+			(l @ Float(_), r) | (l, r @ Float(_)) => Floats(l.to_f64(), r.to_f64()),
+			(l @ Rational(_), r) | (l, r @ Rational(_)) => {
+				Rationals(l.into_rational(), r.into_rational())
+			}
+			(Decimal(d1), Decimal(d2)) => Decimals(d1, d2),
+			(Decimal(d1), Int(i2)) => Decimals(d1, BigInt::from(i2).into()),
+			(Decimal(d1), Big(b2)) => Decimals(d1, b2.into()),
+			(Int(i1), Decimal(d2)) => Decimals(BigInt::from(i1).into(), d2),
+			(Big(b1), Decimal(d2)) => Decimals(b1.into(), d2),
+			(Int(i1), Big(b2)) => Bigs(BigInt::from(i1), b2),
+			(Big(b1), Int(i2)) => Bigs(b1, BigInt::from(i2)),
+			(Big(b1), Big(b2)) => Bigs(b1, b2),
+		}
+	}
+}
+
+impl From<BigInt> for Numeric {
+	fn from(value: BigInt) -> Self {
+		match value.to_i128() {
+			Some(i) => Numeric::Int(i),
+			None => Numeric::Big(value),
+		}
+	}
+}
+
+impl PartialEq for Numeric {
+	fn eq(&self, other: &Self) -> bool {
+		matches!(self.compare(other), Ok(Some(Ordering::Equal)))
+	}
+}
+
+impl Add<Numeric> for Numeric {
+	type Output = Result<Self, NumericError>;
+	fn add(self, rhs: Numeric) -> Self::Output {
+		use Coerced::*;
+		use Numeric::*;
+		let (l, r, dimension) = self.split_matching(rhs)?;
+		let result = match l.coerce(r) {
+			Ints(i1, i2) => i1
+				.checked_add(i2)
+				.map_or_else(|| Self::from(BigInt::from(i1) + i2), Int),
+			Bigs(b1, b2) => Self::from(b1 + b2),
+			Floats(f1, f2) => Float(f1 + f2),
+			Rationals(r1, r2) => Rational(Box::new(r1 + r2)),
+			Decimals(d1, d2) => Decimal(d1 + d2),
+		};
+		Ok(result.with_dimension(dimension))
+	}
+}
+
+impl Sub<Numeric> for Numeric {
+	type Output = Result<Self, NumericError>;
+	fn sub(self, rhs: Numeric) -> Self::Output {
+		use Coerced::*;
+		use Numeric::*;
+		let (l, r, dimension) = self.split_matching(rhs)?;
+		let result = match l.coerce(r) {
+			Ints(i1, i2) => i1
+				.checked_sub(i2)
+				.map_or_else(|| Self::from(BigInt::from(i1) - i2), Int),
+			Bigs(b1, b2) => Self::from(b1 - b2),
+			Floats(f1, f2) => Float(f1 - f2),
+			Rationals(r1, r2) => Rational(Box::new(r1 - r2)),
+			Decimals(d1, d2) => Decimal(d1 - d2),
+		};
+		Ok(result.with_dimension(dimension))
+	}
+}
+
+impl Neg for Numeric {
+	type Output = Self;
+	fn neg(self) -> Self::Output {
+		use Numeric::*;
+		match self {
+			Int(i) => i
+				.checked_neg()
+				.map_or_else(|| Self::from(-BigInt::from(i)), Int),
+			Big(b) => Self::from(-b),
+			Float(f) => Float(-f),
+			Rational(r) => Rational(Box::new(-*r)),
+			Decimal(d) => Decimal(-d),
+			Quantity(q, d) => Quantity(Box::new(-*q), d),
+		}
+	}
+}
+
+impl Mul<Numeric> for Numeric {
+	type Output = Result<Self, NumericError>;
+	fn mul(self, rhs: Numeric) -> Self::Output {
+		use Coerced::*;
+		use Numeric::*;
+		let ((l, d1), (r, d2)) = (self.into_parts(), rhs.into_parts());
+		let dimension = (d1 * d2).ok_or(NumericError::DimensionOverflow(d1, d2))?;
+		let result = match l.coerce(r) {
+			Ints(i1, i2) => i1
+				.checked_mul(i2)
+				.map_or_else(|| Self::from(BigInt::from(i1) * i2), Int),
+			Bigs(b1, b2) => Self::from(b1 * b2),
+			Floats(f1, f2) => Float(f1 * f2),
+			Rationals(r1, r2) => Rational(Box::new(r1 * r2)),
+			Decimals(d1, d2) => Decimal((d1 * d2).ok_or(NumericError::ScaleTooLarge)?),
+		};
+		Ok(result.with_dimension(dimension))
+	}
+}
+
+impl Div<Numeric> for Numeric {
+	type Output = Result<Self, NumericError>;
+	fn div(self, rhs: Numeric) -> Self::Output {
+		use Coerced::*;
+		use Numeric::*;
+		let ((l, d1), (r, d2)) = (self.into_parts(), rhs.into_parts());
+		let dimension = (d1 / d2).ok_or(NumericError::DimensionOverflow(d1, d2))?;
+		let result = match l.coerce(r) {
+			Ints(_, 0) => Err(NumericError::DivisionByZero),
+			Ints(i1, i2) => Ok(i1
+				.checked_div(i2)
+				.map_or_else(|| Self::from(BigInt::from(i1) / i2), Int)),
+			Bigs(_, b2) if b2.is_zero() => Err(NumericError::DivisionByZero),
+			Bigs(b1, b2) => Ok(Self::from(b1 / b2)),
+			Floats(f1, f2) => Ok(Float(f1 / f2)),
+			Rationals(_, r2) if r2.is_zero() => Err(NumericError::DivisionByZero),
+			Rationals(r1, r2) => Ok(Rational(Box::new(r1 / r2))),
+			Decimals(d1, d2) => {
+				let min_scale = d1.scale().max(d2.scale());
+				d1.div(&d2, decimal::DIVISION_SCALE, RoundingMode::HalfEven)
+					.map(|d| Decimal(d.trim(min_scale)))
+					.ok_or(NumericError::DivisionByZero)
+			}
+		};
+		result.map(|quotient| quotient.with_dimension(dimension))
+	}
+}
+
+impl Rem<Numeric> for Numeric {
+	type Output = Result<Self, NumericError>;
+	fn rem(self, rhs: Numeric) -> Self::Output {
+		use Coerced::*;
+		use Numeric::*;
+		let (l, r, dimension) = self.split_matching(rhs)?;
+		let result = match l.coerce(r) {
+			Ints(_, 0) => Err(NumericError::DivisionByZero),
+			Ints(i1, i2) => Ok(i1
+				.checked_rem(i2)
+				.map_or_else(|| Self::from(BigInt::from(i1) % i2), Int)),
+			Bigs(_, b2) if b2.is_zero() => Err(NumericError::DivisionByZero),
+			Bigs(b1, b2) => Ok(Self::from(b1 % b2)),
+			Floats(f1, f2) => Ok(Float(f1 % f2)),
+			Rationals(_, r2) if r2.is_zero() => Err(NumericError::DivisionByZero),
+			Rationals(r1, r2) => Ok(Rational(Box::new(r1 % r2))),
+			Decimals(d1, d2) => d1.rem(&d2).map(Decimal).ok_or(NumericError::DivisionByZero),
+		};
+		result.map(|remainder| remainder.with_dimension(dimension))
+	}
+}
+
+impl Numeric {
+	/// Division rounding toward negative infinity. Rational operands produce
+	/// an integer, like `Int` operands do, and so do quantities.
+	pub fn floor_div(self, rhs: Numeric) -> Result<Self, NumericError> {
+		use Coerced::*;
+		use Numeric::*;
+		let (l, r, _) = self.split_matching(rhs)?;
+		match l.coerce(r) {
+			Ints(_, 0) => Err(NumericError::DivisionByZero),
+			Ints(i1, -1) => Ok(-Int(i1)),
+			Ints(i1, i2) => Ok(Int(Integer::div_floor(&i1, &i2))),
+			Bigs(_, b2) if b2.is_zero() => Err(NumericError::DivisionByZero),
+			Bigs(b1, b2) => Ok(Self::from(b1.div_floor(&b2))),
+			Floats(f1, f2) => Ok(Float((f1 / f2).floor())),
+			Rationals(_, r2) if r2.is_zero() => Err(NumericError::DivisionByZero),
+			Rationals(r1, r2) => Ok(Self::from((r1 / r2).floor().to_integer())),
+			Decimals(d1, d2) => d1
+				.div_mod_floor(&d2)
+				.map(|(quotient, _)| Self::from(quotient))
+				.ok_or(NumericError::DivisionByZero),
+		}
+	}
+
+	/// Remainder of `floor_div`, which always takes the sign of the divisor:
+	/// `-7 % 3 == 2` and `7 % -3 == -2`. The truncating remainder, which takes
+	/// the sign of the dividend, is implemented by `Rem`.
+	pub fn modulo(self, rhs: Numeric) -> Result<Self, NumericError> {
+		use Coerced::*;
+		use Numeric::*;
+		let (l, r, dimension) = self.split_matching(rhs)?;
+		let result = match l.coerce(r) {
+			Ints(_, 0) => Err(NumericError::DivisionByZero),
+			Ints(_, -1) => Ok(Int(0)),
+			Ints(i1, i2) => Ok(Int(i1.mod_floor(&i2))),
+			Bigs(_, b2) if b2.is_zero() => Err(NumericError::DivisionByZero),
+			Bigs(b1, b2) => Ok(Self::from(b1.mod_floor(&b2))),
+			Floats(f1, f2) => {
+				let rem = f1 % f2;
+				if rem != 0.0 && (rem < 0.0) != (f2 < 0.0) {
+					Ok(Float(rem + f2))
+				} else {
+					Ok(Float(rem))
+				}
+			}
+			Rationals(_, r2) if r2.is_zero() => Err(NumericError::DivisionByZero),
+			Rationals(r1, r2) => {
+				let quotient = (&r1 / &r2).floor();
+				Ok(Rational(Box::new(r1 - r2 * quotient)))
+			}
+			Decimals(d1, d2) => d1
+				.div_mod_floor(&d2)
+				.map(|(_, remainder)| Decimal(remainder))
+				.ok_or(NumericError::DivisionByZero),
+		};
+		result.map(|remainder| remainder.with_dimension(dimension))
+	}
+
+	/// Compares two numbers by value, `None` if one of them is NaN.
+	pub fn compare(&self, other: &Numeric) -> Result<Option<Ordering>, NumericError> {
+		use Coerced::*;
+		use Numeric::*;
+		let (l, r, _) = self.clone().split_matching(other.clone())?;
+		Ok(match (l, r) {
+			(Float(f), exact @ (Int(_) | Big(_) | Rational(_) | Decimal(_))) => {
+				Self::compare_float(f, exact)
+			}
+			(exact @ (Int(_) | Big(_) | Rational(_) | Decimal(_)), Float(f)) => {
+				Self::compare_float(f, exact).map(Ordering::reverse)
+			}
+			(l, r) => match l.coerce(r) {
+				Ints(i1, i2) => Some(i1.cmp(&i2)),
+				Bigs(b1, b2) => Some(b1.cmp(&b2)),
+				Floats(f1, f2) => f1.partial_cmp(&f2),
+				Rationals(r1, r2) => Some(r1.cmp(&r2)),
+				Decimals(d1, d2) => Some(d1.cmp(&d2)),
+			},
+		})
+	}
+
+	/// Compares without rounding `exact` to a float, which would make
+	/// `2 ** 53 + 1 == 2.0 ** 53` true.
+	fn compare_float(float: f64, exact: Numeric) -> Option<Ordering> {
+		if float.is_infinite() {
+			return Some(if float > 0.0 {
+				Ordering::Greater
+			} else {
+				Ordering::Less
+			});
+		}
+		Some(BigRational::from_float(float)?.cmp(&exact.into_rational()))
+	}
+
+	fn is_nan(&self) -> bool {
+		matches!(self, Numeric::Float(f) if f.is_nan())
+	}
+
+	/// A total order over all numbers: quantities are ordered by dimension
+	/// first, then by value. `-0.0` and `0.0` are equal, and NaN is equal to
+	/// itself and greater than every other number.
+	pub fn total_cmp(&self, other: &Numeric) -> Ordering {
+		let ((l, d1), (r, d2)) = (self.clone().into_parts(), other.clone().into_parts());
+		d1.cmp(&d2).then_with(|| match l.compare(&r) {
+			Ok(Some(ordering)) => ordering,
+			_ => l.is_nan().cmp(&r.is_nan()),
+		})
+	}
+
+	pub fn abs(self) -> Self {
+		use Numeric::*;
+		match self {
+			Int(i) => i
+				.checked_abs()
+				.map_or_else(|| Self::from(BigInt::from(i).abs()), Int),
+			Big(b) => Self::from(b.abs()),
+			Float(f) => Float(f.abs()),
+			Rational(r) => Rational(Box::new(r.abs())),
+			Decimal(d) => Decimal(d.abs()),
+			Quantity(q, d) => Quantity(Box::new(q.abs()), d),
+		}
+	}
+
+	/// Integers and perfect squares of integers stay exact. The square root of
+	/// a quantity halves the exponents of its unit, which have to be even.
+	pub fn sqrt(self) -> Result<Self, NumericError> {
+		let (magnitude, dimension) = self.into_parts();
+		let root_dimension = dimension
+			.root(2)
+			.ok_or(NumericError::FractionalDimension(dimension))?;
+
+		if let Some(value) = magnitude
+			.clone()
+			.into_big_integer()
+			.filter(|b| !b.is_negative())
+		{
+			let root = value.sqrt();
+			if &root * &root == value {
+				return Ok(Self::from(root).with_dimension(root_dimension));
+			}
+		}
+		Ok(Numeric::Float(magnitude.to_f64().sqrt()).with_dimension(root_dimension))
+	}
+
+	fn round_with(
+		self,
+		float: fn(f64) -> f64,
+		rational: fn(&BigRational) -> BigRational,
+		mode: RoundingMode,
+	) -> Self {
+		use Numeric::*;
+		match self {
+			Int(_) | Big(_) => self,
+			Float(f) => {
+				let rounded = float(f);
+				BigInt::from_f64(rounded).map_or(Float(rounded), Self::from)
+			}
+			Rational(r) => Self::from(rational(&r).to_integer()),
+			Decimal(d) => Self::from(d.to_integer(mode)),
+			Quantity(q, d) => q.round_with(float, rational, mode).with_dimension(d),
+		}
+	}
+
+	/// Rounds toward negative infinity. Finite results are integers.
+	pub fn floor(self) -> Self {
+		self.round_with(f64::floor, BigRational::floor, RoundingMode::Floor)
+	}
+
+	/// Rounds toward positive infinity. Finite results are integers.
+	pub fn ceil(self) -> Self {
+		self.round_with(f64::ceil, BigRational::ceil, RoundingMode::Ceiling)
+	}
+
+	/// Rounds half away from zero. Finite results are integers.
+	pub fn round(self) -> Self {
+		self.round_with(f64::round, BigRational::round, RoundingMode::HalfUp)
+	}
+
+	/// Rounds toward zero. Finite results are integers.
+	pub fn trunc(self) -> Self {
+		self.round_with(f64::trunc, BigRational::trunc, RoundingMode::Down)
+	}
+
+	/// Renders the number with exactly `places` fractional digits, exact
+	/// numbers are rounded half to even. The unit of a quantity is left out,
+	/// and floats that aren't finite render as in `to_text`.
+	pub fn to_fixed(&self, places: u32) -> String {
+		use Numeric::*;
+		match self {
+			Float(f) => Self::non_finite_text(*f)
+				.map_or_else(|| format!("{f:.*}", places as usize), String::from),
+			Int(_) | Big(_) | Decimal(_) => self
+				.to_decimal()
+				.map(|d| d.round(places, RoundingMode::HalfEven).to_string())
+				.unwrap_or_default(),
+			Rational(r) => decimal::Decimal::from(r.numer().to_owned())
+				.div(&r.denom().to_owned().into(), places, RoundingMode::HalfEven)
+				.map(|d| d.to_string())
+				.unwrap_or_default(),
+			Quantity(q, _) => q.to_fixed(places),
+		}
+	}
+
+	/// `None` if one of the operands is not an integer.
+	pub fn gcd(self, rhs: Numeric) -> Option<Self> {
+		let (b1, b2) = (self.into_big_integer()?, rhs.into_big_integer()?);
+		Some(Self::from(b1.gcd(&b2)))
+	}
+
+	/// `None` if one of the operands is not an integer.
+	pub fn lcm(self, rhs: Numeric) -> Option<Self> {
+		let (b1, b2) = (self.into_big_integer()?, rhs.into_big_integer()?);
+		Some(Self::from(b1.lcm(&b2)))
+	}
+}
+
+/// Largest number of bits an exact power may need, so that `2 ** 4000000000`
+/// fails instead of running out of memory.
+const MAX_POWER_BITS: u64 = 1 << 20;
+
+/// Integer, rational and decimal bases raised to a non-negative integer
+/// exponent stay exact, every other combination produces a `Float`. The
+/// exponent has to be dimensionless, and an integer if the base is a quantity.
+/// Exact powers larger than `MAX_POWER_BITS` fail with `ExponentTooLarge`.
+impl Pow<Numeric> for Numeric {
+	type Output = Result<Self, NumericError>;
+	fn pow(self, rhs: Numeric) -> Self::Output {
+		use Numeric::*;
+
+		if let Quantity(_, exponent_dimension) = rhs {
+			return Err(NumericError::DimensionMismatch(
+				exponent_dimension,
+				Dimension::NONE,
+			));
+		}
+		let (base, dimension) = self.into_parts();
+		let dimension = match &rhs {
+			_ if dimension.is_none() => dimension,
+			Int(e) => i32::try_from(*e)
+				.ok()
+				.and_then(|e| dimension.pow(e))
+				.ok_or_else(|| NumericError::ExponentTooLarge(rhs.clone()))?,
+			Big(_) => return Err(NumericError::ExponentTooLarge(rhs)),
+			_ => return Err(NumericError::FractionalDimension(dimension)),
+		};
+
+		let exact_exponent = match &rhs {
+			Int(e) if *e >= 0 => Some(u32::try_from(*e).ok()),
+			Big(e) if e.is_positive() => Some(u32::try_from(e).ok()),
+			_ => None,
+		};
+
+		if let Some(Some(e)) = exact_exponent {
+			let bits = match &base {
+				Int(i) => u64::from(i128::BITS - i.unsigned_abs().leading_zeros()),
+				Big(b) => b.bits(),
+				Rational(r) => r.numer().bits().max(r.denom().bits()),
+				Decimal(d) => d.bits(),
+				Float(_) | Quantity(..) => 0,
+			};
+			if bits > 1 && bits * u64::from(e) > MAX_POWER_BITS {
+				return Err(NumericError::ExponentTooLarge(rhs));
+			}
+		}
+
+		let power = match (base, exact_exponent) {
+			(base @ (Float(_) | Quantity(..)), _) | (base, None) => {
+				Float(base.to_f64().powf(rhs.to_f64()))
+			}
+			(_, Some(None)) => return Err(NumericError::ExponentTooLarge(rhs)),
+			(Int(i), Some(Some(e))) => i
+				.checked_pow(e)
+				.map_or_else(|| Self::from(BigInt::from(i).pow(e)), Int),
+			(Big(b), Some(Some(e))) => Self::from(b.pow(e)),
+			(Rational(r), Some(Some(e))) => Rational(Box::new(r.pow(e))),
+			(Decimal(d), Some(Some(e))) => match d.pow(e) {
+				Some(power) => Decimal(power),
+				None => return Err(NumericError::ExponentTooLarge(rhs)),
+			},
+		};
+		Ok(power.with_dimension(dimension))
+	}
+}
+
+impl BitAnd<Numeric> for Numeric {
+	type Output = Option<Self>;
+	fn bitand(self, rhs: Numeric) -> Self::Output {
+		use Coerced::*;
+		use Numeric::*;
+		if self.is_quantity() || rhs.is_quantity() {
+			return None;
+		}
+		match self.coerce(rhs) {
+			Ints(i1, i2) => Some(Int(i1 & i2)),
+			Bigs(b1, b2) => Some(Self::from(b1 & b2)),
+			Floats(..) | Rationals(..) | Decimals(..) => None,
+		}
+	}
+}
+
+impl BitOr<Numeric> for Numeric {
+	type Output = Option<Self>;
+	fn bitor(self, rhs: Numeric) -> Self::Output {
+		use Coerced::*;
+		use Numeric::*;
+		if self.is_quantity() || rhs.is_quantity() {
+			return None;
+		}
+		match self.coerce(rhs) {
+			Ints(i1, i2) => Some(Int(i1 | i2)),
+			Bigs(b1, b2) => Some(Self::from(b1 | b2)),
+			Floats(..) | Rationals(..) | Decimals(..) => None,
+		}
+	}
+}
+
+impl BitXor<Numeric> for Numeric {
+	type Output = Option<Self>;
+	fn bitxor(self, rhs: Numeric) -> Self::Output {
+		use Coerced::*;
+		use Numeric::*;
+		if self.is_quantity() || rhs.is_quantity() {
+			return None;
+		}
+		match self.coerce(rhs) {
+			Ints(i1, i2) => Some(Int(i1 ^ i2)),
+			Bigs(b1, b2) => Some(Self::from(b1 ^ b2)),
+			Floats(..) | Rationals(..) | Decimals(..) => None,
+		}
+	}
+}
+
+impl Not for Numeric {
+	type Output = Option<Self>;
+	fn not(self) -> Self::Output {
+		use Numeric::*;
+		match self {
+			Int(i) => Some(Int(!i)),
+			Big(b) => Some(Self::from(!b)),
+			Float(_) | Rational(_) | Decimal(_) | Quantity(..) => None,
+		}
+	}
+}
+
+/// Largest shift amount, a few hundred thousand bits.
+const MAX_SHIFT: i128 = 1 << 18;
+
+/// Shifts work on integers of any size, the shift amount has to be in
+/// `0..=MAX_SHIFT`. `None` means one of the operands is not an integer.
+impl Shl<Numeric> for Numeric {
+	type Output = Option<Result<Self, NumericError>>;
+	fn shl(self, rhs: Numeric) -> Self::Output {
+		let value = self.into_big_integer()?;
+		let amount = rhs.shift_amount()?;
+		Some(amount.map(|amount| Self::from(value << amount)))
+	}
+}
+
+impl Shr<Numeric> for Numeric {
+	type Output = Option<Result<Self, NumericError>>;
+	fn shr(self, rhs: Numeric) -> Self::Output {
+		let value = self.into_big_integer()?;
+		let amount = rhs.shift_amount()?;
+		Some(amount.map(|amount| Self::from(value >> amount)))
+	}
+}
+
+impl Numeric {
+	/// `inf`, `-inf` and `nan` for floats that aren't finite.
+	fn non_finite_text(fl: f64) -> Option<&'static str> {
+		match fl {
+			_ if fl.is_nan() => Some("nan"),
+			f64::INFINITY => Some("inf"),
+			f64::NEG_INFINITY => Some("-inf"),
+			_ => None,
+		}
+	}
+
+	/// The number as it is embedded in text. Unlike `Display`, which echoes
+	/// infinities and NaN as the constants of the math namespace so they read
+	/// as source code, this writes them as `inf`, `-inf` and `nan`.
+	pub fn to_text(&self) -> String {
+		match self {
+			Numeric::Float(fl) => {
+				Self::non_finite_text(*fl).map_or_else(|| self.to_string(), String::from)
+			}
+			Numeric::Quantity(q, d) => format!("{} {d}", q.to_text()),
+			other => other.to_string(),
+		}
+	}
+
+	/// Floats always show a decimal point, as in `1.0e20`, so they can't be
+	/// confused with integers and parse back to the same value. Infinities
+	/// and NaN show as the constants of the math namespace.
+	fn fmt_float(fl: f64, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if fl.is_nan() {
+			return f.write_str("math.nan");
+		}
+		if fl.is_infinite() {
+			return f.write_str(if fl > 0.0 { "math.inf" } else { "-math.inf" });
+		}
+		let text = format!("{fl:?}");
+		match text.split_once('e') {
+			Some((mantissa, exponent)) if !mantissa.contains('.') => {
+				write!(f, "{mantissa}.0e{exponent}")
+			}
+			_ => f.write_str(&text),
+		}
+	}
+}
+
+impl Display for Numeric {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		use Numeric::*;
+
+		match self {
+			Int(i) => f.write_str(&i.to_string()),
+			Big(b) => f.write_str(&b.to_string()),
+			Float(fl) => Self::fmt_float(*fl, f),
+			Rational(r) if r.is_integer() => write!(f, "{}", r.numer()),
+			Rational(r) => write!(f, "{}/{}", r.numer(), r.denom()),
+			Decimal(d) => Display::fmt(d, f),
+			Quantity(q, d) => write!(f, "{q} {d}"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use num_bigint::BigInt;
+	use num_rational::BigRational;
+	use num_traits::Pow;
+
+	use super::{
+		error::NumericError,
+		Numeric::{self, *},
+	};
+
+	fn big(src: &str) -> Numeric {
+		Numeric::from(src.parse::<BigInt>().unwrap())
+	}
+
+	fn rational(numer: i128, denom: i128) -> Numeric {
+		Rational(Box::new(BigRational::new(numer.into(), denom.into())))
+	}
+
+	#[test]
+	fn overflow_promotes_to_big() {
+		let sum = (Int(i128::MAX) + Int(1)).unwrap();
+		assert!(matches!(sum, Big(_)));
+		assert_eq!(sum.to_string(), "170141183460469231731687303715884105728");
+
+		assert!(matches!((Int(i128::MIN) - Int(1)).unwrap(), Big(_)));
+		assert!(matches!(Int(i128::MAX) * Int(2), Ok(Big(_))));
+		assert!(matches!(-Int(i128::MIN), Big(_)));
+	}
+
+	#[test]
+	fn big_results_demote_to_int() {
+		let max = (big("170141183460469231731687303715884105728") - Int(1)).unwrap();
+		assert!(matches!(max, Int(i128::MAX)));
+		assert!(matches!(Numeric::from(BigInt::from(7)), Int(7)));
+		assert!(matches!(
+			(big("340282366920938463463374607431768211456") / Int(i128::MAX)).unwrap(),
+			Int(2)
+		));
+	}
+
+	#[test]
+	fn mixed_operands_coerce_to_the_wider_variant() {
+		assert!(matches!((Int(1) + Float(0.5)).unwrap(), Float(f) if f == 1.5));
+		assert!(matches!(
+			(big(&"1".repeat(40)) + Float(0.0)).unwrap(),
+			Float(_)
+		));
+		assert!(matches!((Int(7) / Int(2)).unwrap(), Int(3)));
+	}
+
+	#[test]
+	fn rationals_stay_exact_in_lowest_terms() {
+		let sum = (rational(1, 2) + rational(1, 3)).unwrap();
+		assert_eq!(sum.to_string(), "5/6");
+		assert_eq!((rational(2, 4) + Int(1)).unwrap().to_string(), "3/2");
+		assert_eq!((Int(1) / rational(3, 1)).unwrap().to_string(), "1/3");
+		assert!(matches!((rational(1, 2) + Float(0.25)).unwrap(), Float(f) if f == 0.75));
+	}
+
+	#[test]
+	fn whole_rationals_display_as_integers() {
+		assert_eq!(rational(3, 1).to_string(), "3");
+		assert_eq!(rational(-2000, 2).to_string(), "-1000");
+		assert_eq!((rational(1, 2) + rational(1, 2)).unwrap().to_string(), "1");
+		assert_eq!(rational(0, 5).to_string(), "0");
+	}
+
+	#[test]
+	fn rational_division_by_zero_fails() {
+		assert!(matches!(
+			rational(1, 2) / rational(0, 1),
+			Err(NumericError::DivisionByZero)
+		));
+	}
+
+	#[test]
+	fn parse_rational_is_exact() {
+		assert_eq!(Numeric::parse_rational("0.1").unwrap().to_string(), "1/10");
+		assert_eq!(
+			Numeric::parse_rational("2.5e-3").unwrap().to_string(),
+			"1/400"
+		);
+		assert_eq!(Numeric::parse_rational("7e2").unwrap().to_string(), "700");
+	}
+
+	#[test]
+	fn exact_powers_promote_to_big() {
+		let power = Int(2).pow(Int(200)).unwrap();
+		assert!(matches!(power, Big(_)));
+		assert_eq!(
+			power.to_string(),
+			format!("{}", BigInt::from(2).pow(200u32))
+		);
+		assert_eq!(rational(2, 3).pow(Int(2)).unwrap().to_string(), "4/9");
+		assert!(matches!(Int(2).pow(Int(-1)).unwrap(), Float(f) if f == 0.5));
+	}
+
+	#[test]
+	fn huge_exponents_fail() {
+		assert!(matches!(
+			Int(2).pow(Int(4_000_000_000)),
+			Err(NumericError::ExponentTooLarge(_))
+		));
+		assert!(matches!(
+			Int(2).pow(big(&"9".repeat(40))),
+			Err(NumericError::ExponentTooLarge(_))
+		));
+		let tenth = Numeric::parse_decimal("0.1").unwrap();
+		assert!(matches!(
+			tenth.pow(Int(4_000_000_000)),
+			Err(NumericError::ExponentTooLarge(_))
+		));
+		// powers of 1 and -1 stay small whatever the exponent
+		assert!(matches!(Int(-1).pow(Int(4_000_000_001)).unwrap(), Int(-1)));
+	}
+
+	#[test]
+	fn squaring_tiny_decimals_fails() {
+		let mut square = Numeric::parse_decimal("1e-32767").unwrap();
+		let error = loop {
+			match square.clone() * square {
+				Ok(product) => square = product,
+				Err(error) => break error,
+			}
+		};
+		assert!(matches!(error, NumericError::ScaleTooLarge));
+	}
+
+	#[test]
+	fn floor_division_rounds_toward_negative_infinity() {
+		assert!(matches!(Int(-7).floor_div(Int(2)).unwrap(), Int(-4)));
+		assert!(matches!(Int(7).floor_div(Int(-2)).unwrap(), Int(-4)));
+		assert!(matches!(Float(7.5).floor_div(Int(2)).unwrap(), Float(f) if f == 3.0));
+		assert!(matches!(rational(7, 2).floor_div(Int(1)).unwrap(), Int(3)));
+		assert!(matches!(Int(i128::MIN).floor_div(Int(-1)).unwrap(), Big(_)));
+		assert!(matches!(
+			Int(1).floor_div(Int(0)),
+			Err(NumericError::DivisionByZero)
+		));
+	}
+
+	#[test]
+	fn modulo_takes_the_sign_of_the_divisor() {
+		assert!(matches!(Int(-7).modulo(Int(3)).unwrap(), Int(2)));
+		assert!(matches!(Int(7).modulo(Int(-3)).unwrap(), Int(-2)));
+		assert!(matches!((Int(-7) % Int(3)).unwrap(), Int(-1)));
+		assert!(matches!(Float(-1.5).modulo(Int(1)).unwrap(), Float(f) if f == 0.5));
+		assert_eq!(rational(-1, 2).modulo(Int(1)).unwrap().to_string(), "1/2");
+		assert!(matches!(Int(i128::MIN).modulo(Int(-1)).unwrap(), Int(0)));
+	}
+
+	#[test]
+	fn shifts_grow_into_big_integers() {
+		let shifted = (Int(1) << Int(130)).unwrap().unwrap();
+		assert_eq!(shifted.to_string(), BigInt::from(2).pow(130u32).to_string());
+		assert!(matches!((shifted >> Int(129)).unwrap().unwrap(), Int(2)));
+		assert!(matches!((Int(-8) >> Int(1)).unwrap().unwrap(), Int(-4)));
+		assert!(matches!((Int(-1) >> Int(200)).unwrap().unwrap(), Int(-1)));
+	}
+
+	#[test]
+	fn shift_amounts_are_checked() {
+		for amount in [Int(-1), Int(4_000_000_000), big(&"9".repeat(40))] {
+			assert!(matches!(
+				(Int(1) << amount).unwrap(),
+				Err(NumericError::ShiftOutOfRange(_))
+			));
+		}
+		assert!((Float(1.5) << Int(1)).is_none());
+		assert!((Int(1) << Float(1.0)).is_none());
+	}
+
+	#[test]
+	fn bitwise_operators_need_integers() {
+		assert!(matches!((Int(5) & Int(3)).unwrap(), Int(1)));
+		assert!(matches!((Int(5) | Int(3)).unwrap(), Int(7)));
+		assert!(matches!((Int(5) ^ Int(3)).unwrap(), Int(6)));
+		assert!(matches!((!Int(5)).unwrap(), Int(-6)));
+		assert!((Float(1.5) & Int(1)).is_none());
+	}
+
+	#[test]
+	fn floats_display_so_they_parse_back() {
+		assert_eq!(Float(1.0).to_string(), "1.0");
+		assert_eq!(Float(1e20).to_string(), "1.0e20");
+		assert_eq!(Float(1.5e-7).to_string(), "1.5e-7");
+		assert_eq!(Float(f64::INFINITY).to_string(), "math.inf");
+		assert_eq!(Float(f64::NEG_INFINITY).to_string(), "-math.inf");
+		assert_eq!(Float(f64::NAN).to_string(), "math.nan");
+	}
+
+	#[test]
+	fn non_finite_floats_as_text() {
+		assert_eq!(Float(f64::INFINITY).to_text(), "inf");
+		assert_eq!(Float(f64::NEG_INFINITY).to_text(), "-inf");
+		assert_eq!(Float(f64::NAN).to_text(), "nan");
+		assert_eq!(Float(f64::NAN).to_fixed(2), "nan");
+		assert_eq!(Float(f64::NEG_INFINITY).to_fixed(2), "-inf");
+		assert_eq!(Float(1.5).to_text(), "1.5");
+	}
+}
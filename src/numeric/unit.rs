@@ -0,0 +1,251 @@
+use std::{
+	fmt::Display,
+	ops::{Div, Mul},
+};
+
+use num_bigint::BigInt;
+
+use super::{decimal::Decimal, error::NumericError, Numeric};
+
+const BASE_UNITS: [&str; 7] = ["m", "kg", "s", "A", "K", "mol", "cd"];
+
+/// Exponents of the SI base units, in the order of `BASE_UNITS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Dimension([i32; 7]);
+
+const LENGTH: Dimension = Dimension::base(0);
+const MASS: Dimension = Dimension::base(1);
+const TIME: Dimension = Dimension::base(2);
+const CURRENT: Dimension = Dimension::base(3);
+const TEMPERATURE: Dimension = Dimension::base(4);
+const AMOUNT: Dimension = Dimension::base(5);
+const LUMINOSITY: Dimension = Dimension::base(6);
+
+impl Dimension {
+	pub const NONE: Self = Self([0; 7]);
+
+	const fn base(idx: usize) -> Self {
+		let mut exponents = [0; 7];
+		exponents[idx] = 1;
+		Self(exponents)
+	}
+
+	pub fn is_none(&self) -> bool {
+		*self == Self::NONE
+	}
+
+	/// `None` if an exponent overflows.
+	pub fn pow(self, exponent: i32) -> Option<Self> {
+		let mut exponents = self.0;
+		for e in &mut exponents {
+			*e = e.checked_mul(exponent)?;
+		}
+		Some(Self(exponents))
+	}
+
+	/// `None` if an exponent is not divisible by `degree`.
+	pub fn root(self, degree: i32) -> Option<Self> {
+		let mut exponents = self.0;
+		for e in &mut exponents {
+			if *e % degree != 0 {
+				return None;
+			}
+			*e /= degree;
+		}
+		Some(Self(exponents))
+	}
+
+	fn combine(self, rhs: Dimension, op: fn(i32, i32) -> Option<i32>) -> Option<Self> {
+		let mut exponents = self.0;
+		for (e, rhs) in exponents.iter_mut().zip(rhs.0) {
+			*e = op(*e, rhs)?;
+		}
+		Some(Self(exponents))
+	}
+}
+
+/// `None` if an exponent overflows.
+impl Mul<Dimension> for Dimension {
+	type Output = Option<Self>;
+	fn mul(self, rhs: Dimension) -> Self::Output {
+		self.combine(rhs, i32::checked_add)
+	}
+}
+
+/// `None` if an exponent overflows.
+impl Div<Dimension> for Dimension {
+	type Output = Option<Self>;
+	fn div(self, rhs: Dimension) -> Self::Output {
+		self.combine(rhs, i32::checked_sub)
+	}
+}
+
+/// Prints `kg*m/s^2` style units. Without any positive exponent the negative
+/// ones are written out, as in `s^-1`.
+impl Display for Dimension {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let units = |keep: fn(i32) -> bool, sign: i32| {
+			BASE_UNITS
+				.iter()
+				.zip(self.0)
+				.filter(|(_, e)| keep(*e))
+				.map(|(symbol, e)| match e * sign {
+					1 => symbol.to_string(),
+					e => format!("{symbol}^{e}"),
+				})
+				.collect::<Vec<_>>()
+		};
+
+		let numer = units(|e| e > 0, 1);
+		let denom = units(|e| e < 0, -1);
+		match (numer.len(), denom.len()) {
+			(0, 0) => f.write_str("1"),
+			(0, _) => f.write_str(&units(|e| e != 0, 1).join("*")),
+			(_, 0) => f.write_str(&numer.join("*")),
+			(_, 1) => write!(f, "{}/{}", numer.join("*"), denom[0]),
+			_ => write!(f, "{}/({})", numer.join("*"), denom.join("*")),
+		}
+	}
+}
+
+/// A unit symbol that can follow a number literal, as a multiple of the SI
+/// base units: the unit is `factor * 10^-scale` of `dimension`.
+#[derive(Debug)]
+pub struct Unit {
+	factor: i128,
+	scale: u32,
+	dimension: Dimension,
+}
+
+impl Unit {
+	const fn new(factor: i128, scale: u32, dimension: Dimension) -> Self {
+		Self {
+			factor,
+			scale,
+			dimension,
+		}
+	}
+
+	/// Converts the magnitude to base units, so `5 km` is `5000 m`. Integer
+	/// magnitudes become `Decimal`s, which keeps `5 m / 2 s` from truncating.
+	pub fn apply(&self, magnitude: Numeric) -> Result<Numeric, NumericError> {
+		let factor = Decimal::new(BigInt::from(self.factor), self.scale);
+		Ok((magnitude * Numeric::Decimal(factor))?.with_dimension(self.dimension))
+	}
+}
+
+static UNITS: phf::Map<&'static str, Unit> = phf::phf_map! {
+	"m" => Unit::new(1, 0, LENGTH),
+	"km" => Unit::new(1000, 0, LENGTH),
+	"cm" => Unit::new(1, 2, LENGTH),
+	"mm" => Unit::new(1, 3, LENGTH),
+	"kg" => Unit::new(1, 0, MASS),
+	"g" => Unit::new(1, 3, MASS),
+	"s" => Unit::new(1, 0, TIME),
+	"ms" => Unit::new(1, 3, TIME),
+	"min" => Unit::new(60, 0, TIME),
+	"h" => Unit::new(3600, 0, TIME),
+	"A" => Unit::new(1, 0, CURRENT),
+	"K" => Unit::new(1, 0, TEMPERATURE),
+	"mol" => Unit::new(1, 0, AMOUNT),
+	"cd" => Unit::new(1, 0, LUMINOSITY),
+};
+
+pub fn lookup(symbol: &str) -> Option<&'static Unit> {
+	UNITS.get(symbol)
+}
+
+#[cfg(test)]
+mod tests {
+	use num_traits::Pow;
+
+	use super::{lookup, Dimension, LENGTH, MASS, TIME};
+	use crate::numeric::{error::NumericError, Numeric};
+
+	fn quantity(magnitude: i128, symbol: &str) -> Numeric {
+		lookup(symbol)
+			.unwrap()
+			.apply(Numeric::Int(magnitude))
+			.unwrap()
+	}
+
+	#[test]
+	fn units_convert_to_base_units() {
+		assert_eq!(quantity(5, "km").to_string(), "5000 m");
+		assert_eq!(quantity(90, "min").to_string(), "5400 s");
+		assert_eq!(quantity(250, "g").to_string(), "0.250 kg");
+		assert!(lookup("furlong").is_none());
+	}
+
+	#[test]
+	fn dimensions_display_as_fractions() {
+		assert_eq!(Dimension::NONE.to_string(), "1");
+		let force = (MASS * LENGTH).and_then(|d| d / TIME.pow(2)?).unwrap();
+		assert_eq!((LENGTH / TIME).unwrap().to_string(), "m/s");
+		assert_eq!(force.to_string(), "m*kg/s^2");
+		assert_eq!((Dimension::NONE / TIME).unwrap().to_string(), "s^-1");
+		assert_eq!(LENGTH.pow(3).unwrap().to_string(), "m^3");
+		assert_eq!(LENGTH.pow(2).unwrap().root(2), Some(LENGTH));
+		assert_eq!(LENGTH.root(2), None);
+	}
+
+	#[test]
+	fn exponent_overflow_is_an_error() {
+		let huge = LENGTH.pow(i32::MAX).unwrap();
+		assert_eq!(huge * LENGTH, None);
+		assert_eq!(Dimension::NONE / huge, Some(LENGTH.pow(-i32::MAX).unwrap()));
+		assert_eq!(huge.pow(-1).unwrap() / huge, None);
+
+		let metres = Numeric::Int(1).with_dimension(huge);
+		assert!(matches!(
+			metres * quantity(1, "m"),
+			Err(NumericError::DimensionOverflow(..))
+		));
+	}
+
+	#[test]
+	fn addition_needs_matching_dimensions() {
+		let sum = (quantity(5, "km") + quantity(3, "m")).unwrap();
+		assert_eq!(sum.to_string(), "5003 m");
+		assert!(matches!(
+			quantity(5, "m") + quantity(2, "s"),
+			Err(NumericError::DimensionMismatch(..))
+		));
+		assert!(matches!(
+			quantity(5, "m") + Numeric::Int(1),
+			Err(NumericError::DimensionMismatch(..))
+		));
+		assert!(matches!(
+			quantity(5, "m").compare(&quantity(2, "s")),
+			Err(NumericError::DimensionMismatch(..))
+		));
+	}
+
+	#[test]
+	fn products_combine_dimensions() {
+		let speed = (quantity(5, "m") / quantity(2, "s")).unwrap();
+		assert_eq!(speed.to_string(), "2.5 m/s");
+		assert_eq!(
+			(quantity(5, "m") * quantity(2, "m")).unwrap().to_string(),
+			"10 m^2"
+		);
+		// dimensions that cancel out leave a plain number
+		let ratio = (quantity(4, "m") / quantity(2, "m")).unwrap();
+		assert!(!ratio.is_quantity());
+	}
+
+	#[test]
+	fn powers_of_quantities() {
+		let area = quantity(3, "m").pow(Numeric::Int(2)).unwrap();
+		assert_eq!(area.to_string(), "9 m^2");
+		assert_eq!(area.sqrt().unwrap().to_string(), "3.0 m");
+		assert!(matches!(
+			quantity(2, "m").pow(Numeric::Float(0.5)),
+			Err(NumericError::FractionalDimension(_))
+		));
+		assert!(matches!(
+			Numeric::Int(2).pow(quantity(1, "m")),
+			Err(NumericError::DimensionMismatch(..))
+		));
+	}
+}
@@ -1,7 +1,6 @@
-use std::{
-	fmt::Display,
-	num::{ParseFloatError, ParseIntError},
-};
+use std::{fmt::Display, num::ParseFloatError};
+
+use num_bigint::ParseBigIntError;
 
 use crate::lexer::{error::LexerError, token::Token};
 
@@ -10,9 +9,15 @@ pub enum ParserError {
 	UnexpectedEOF,
 	UnexpectedToken(Token),
 	Lexer(LexerError),
-	ParseInt(ParseIntError),
+	ParseInt(ParseBigIntError),
 	ParseFloat(ParseFloatError),
+	InvalidNumber(Box<str>),
 	ExpectedCloseParen,
+	ExpectedCloseBracket,
+	ExpectedCloseBrace,
+	DuplicateField(Box<str>),
+	DuplicateVariant(Box<str>),
+	OutsideLoop(Token),
 }
 
 impl From<LexerError> for ParserError {
@@ -20,8 +25,8 @@ impl From<LexerError> for ParserError {
 		Self::Lexer(value)
 	}
 }
-impl From<ParseIntError> for ParserError {
-	fn from(value: ParseIntError) -> Self {
+impl From<ParseBigIntError> for ParserError {
+	fn from(value: ParseBigIntError) -> Self {
 		Self::ParseInt(value)
 	}
 }
@@ -39,7 +44,13 @@ impl Display for ParserError {
 			ParserError::Lexer(lexer_error) => Display::fmt(lexer_error, f),
 			ParserError::ParseInt(parse_int_error) => Display::fmt(parse_int_error, f),
 			ParserError::ParseFloat(parse_float_error) => Display::fmt(parse_float_error, f),
+			ParserError::InvalidNumber(num) => write!(f, "Invalid number literal: {num}"),
 			ParserError::ExpectedCloseParen => f.write_str("Expected a closing parenthesis"),
+			ParserError::ExpectedCloseBracket => f.write_str("Expected a closing bracket"),
+			ParserError::ExpectedCloseBrace => f.write_str("Expected a closing brace"),
+			ParserError::DuplicateField(ident) => write!(f, "Field '{ident}' is given twice"),
+			ParserError::DuplicateVariant(ident) => write!(f, "Variant '{ident}' is given twice"),
+			ParserError::OutsideLoop(tk) => write!(f, "'{tk}' outside of a loop"),
 		}
 	}
 }
@@ -1,23 +1,41 @@
 pub mod error;
+pub mod warning;
 
-use std::{fmt::Display, ops::Not};
+use std::{collections::HashMap, fmt::Display, ops::Not};
+
+use num_bigint::BigInt;
+use num_traits::Num;
 
 use error::ParserError::{self, *};
+use warning::ParserWarning;
 
 use crate::{
 	expression::{
 		assignment::AssignmentExpression,
 		binary::{BinaryExpression, BinaryOp},
+		call::CallExpression,
 		declaration::DeclarationExpression,
+		enumeration::{EnumDeclaration, VariantPath},
+		index::IndexExpression,
+		interpolation::{InterpolationExpression, InterpolationPart},
+		looping::ForExpression,
+		matching::{MatchArm, MatchExpression},
+		member::MemberExpression,
+		pattern::Pattern,
+		range::RangeExpression,
+		structure::{StructDeclaration, StructLiteral},
 		unary::{UnaryExpression, UnaryOp},
 		Expression,
 	},
 	helpers::iter_to_string,
 	lexer::{
-		token::Token::{self, *},
+		token::{
+			NumberKind, StringPart,
+			Token::{self, *},
+		},
 		Lexer,
 	},
-	numeric::Numeric,
+	numeric::{unit, Numeric},
 };
 
 type ParserResult = std::result::Result<Expression, ParserError>;
@@ -26,6 +44,15 @@ type ParserResult = std::result::Result<Expression, ParserError>;
 pub struct Parser {
 	idx: usize,
 	tokens: Vec<Token>,
+	/// Whether `name {` starts a struct literal, which it doesn't right before
+	/// a block, as in `match value { ... }`.
+	struct_literals: bool,
+	/// The variants of the enums declared so far, to check matches on them.
+	enums: HashMap<Box<str>, Vec<Box<str>>>,
+	warnings: Vec<ParserWarning>,
+	/// How many loops the current expression is in, `break` and `continue`
+	/// need at least one.
+	loops: usize,
 }
 
 impl Display for Parser {
@@ -47,9 +74,18 @@ impl Parser {
 		Self {
 			idx: 0,
 			tokens: Vec::new(),
+			struct_literals: true,
+			enums: HashMap::new(),
+			warnings: Vec::new(),
+			loops: 0,
 		}
 	}
 
+	/// The warnings about the last parsed input.
+	pub fn take_warnings(&mut self) -> Vec<ParserWarning> {
+		std::mem::take(&mut self.warnings)
+	}
+
 	fn eof(&self) -> bool {
 		self.idx >= self.tokens.len()
 	}
@@ -67,10 +103,8 @@ impl Parser {
 	}
 
 	pub fn produce_ast(&mut self, src: &str, keep_tokens: bool) -> ParserResult {
-		let new_tokens = Lexer::tokenize(src).map_err(|err| {
-			self.clear(keep_tokens);
-			err
-		})?;
+		self.warnings.clear();
+		let new_tokens = Lexer::tokenize(src).inspect_err(|_| self.clear(keep_tokens))?;
 		self.tokens.extend(new_tokens);
 
 		let mut program = Vec::new();
@@ -98,35 +132,35 @@ impl Parser {
 		res.map(Expression::Program)
 	}
 
+	/// Binary operators bind as in C, from loosest to tightest: `|`, `^`, `&`,
+	/// equality, relational, ranges, shifts, additive, multiplicative and `**`.
+	/// So `a & b == c` is `a & (b == c)`.
 	fn parse_expression(&mut self) -> ParserResult {
-		self.parse_equality()
+		self.parse_bit_or()
 	}
 
-	fn parse_equality(&mut self) -> ParserResult {
-		let mut left = self.parse_additive()?;
-		while let Some(Equals) = self.current() {
-			self.advance(1);
-			let right = self.parse_additive()?;
-			let expr = BinaryExpression {
-				left: Box::new(left),
-				right: Box::new(right),
-				operator: BinaryOp::Equals,
-			};
-			left = Expression::Binary(expr);
-		}
-		Ok(left)
+	/// Parses an expression inside brackets, where struct literals are always
+	/// allowed.
+	fn parse_nested(&mut self) -> ParserResult {
+		self.parse_restricted(true)
 	}
 
-	fn parse_additive(&mut self) -> ParserResult {
-		let mut left = self.parse_multiplicative()?;
-		while let Some(operator) = self.current() {
-			let operator = match operator {
-				Plus => BinaryOp::Add,
-				Minus => BinaryOp::Subtract,
-				_ => break,
-			};
+	fn parse_restricted(&mut self, struct_literals: bool) -> ParserResult {
+		let outer = std::mem::replace(&mut self.struct_literals, struct_literals);
+		let expr = self.parse_expression();
+		self.struct_literals = outer;
+		expr
+	}
+
+	fn parse_left_associative(
+		&mut self,
+		operand: fn(&mut Self) -> ParserResult,
+		operator: fn(&Token) -> Option<BinaryOp>,
+	) -> ParserResult {
+		let mut left = operand(self)?;
+		while let Some(operator) = self.current().and_then(operator) {
 			self.advance(1);
-			let right = self.parse_multiplicative()?;
+			let right = operand(self)?;
 			let expr = BinaryExpression {
 				left: Box::new(left),
 				right: Box::new(right),
@@ -137,58 +171,154 @@ impl Parser {
 		Ok(left)
 	}
 
+	fn parse_equality(&mut self) -> ParserResult {
+		self.parse_left_associative(Self::parse_relational, |token| match token {
+			Equals => Some(BinaryOp::Equals),
+			NotEquals => Some(BinaryOp::NotEquals),
+			_ => None,
+		})
+	}
+
+	fn parse_relational(&mut self) -> ParserResult {
+		self.parse_left_associative(Self::parse_range, |token| match token {
+			Less => Some(BinaryOp::Less),
+			LessEqual => Some(BinaryOp::LessEqual),
+			Greater => Some(BinaryOp::Greater),
+			GreaterEqual => Some(BinaryOp::GreaterEqual),
+			_ => None,
+		})
+	}
+
+	/// Ranges don't chain, so `a..b..c` is an error.
+	fn parse_range(&mut self) -> ParserResult {
+		let start = self.parse_shift()?;
+		let inclusive = match self.current() {
+			Some(DotDot) => false,
+			Some(DotDotEqual) => true,
+			_ => return Ok(start),
+		};
+		self.advance(1);
+		let end = self.parse_shift()?;
+		// `step` is only special here, elsewhere it is a plain name
+		let step = match self.current() {
+			Some(Identifier(ident)) if &**ident == "step" => {
+				self.advance(1);
+				Some(Box::new(self.parse_shift()?))
+			}
+			_ => None,
+		};
+		Ok(Expression::Range(RangeExpression {
+			start: Box::new(start),
+			end: Box::new(end),
+			step,
+			inclusive,
+		}))
+	}
+
+	fn parse_bit_or(&mut self) -> ParserResult {
+		self.parse_left_associative(Self::parse_bit_xor, |token| match token {
+			Pipe => Some(BinaryOp::BitOr),
+			_ => None,
+		})
+	}
+
+	fn parse_bit_xor(&mut self) -> ParserResult {
+		self.parse_left_associative(Self::parse_bit_and, |token| match token {
+			Caret => Some(BinaryOp::BitXor),
+			_ => None,
+		})
+	}
+
+	fn parse_bit_and(&mut self) -> ParserResult {
+		self.parse_left_associative(Self::parse_equality, |token| match token {
+			Ampersand => Some(BinaryOp::BitAnd),
+			_ => None,
+		})
+	}
+
+	fn parse_shift(&mut self) -> ParserResult {
+		self.parse_left_associative(Self::parse_additive, |token| match token {
+			DoubleLess => Some(BinaryOp::ShiftLeft),
+			DoubleGreater => Some(BinaryOp::ShiftRight),
+			_ => None,
+		})
+	}
+
+	fn parse_additive(&mut self) -> ParserResult {
+		self.parse_left_associative(Self::parse_multiplicative, |token| match token {
+			Plus => Some(BinaryOp::Add),
+			Minus => Some(BinaryOp::Subtract),
+			_ => None,
+		})
+	}
+
 	fn parse_multiplicative(&mut self) -> ParserResult {
-		let mut left = self.parse_unary()?;
-		while let Some(operator) = self.current() {
-			let operator = match operator {
-				Star => BinaryOp::Multiply,
-				Slash => BinaryOp::Divide,
-				Percent => BinaryOp::Modulo,
-				_ => break,
-			};
-			self.advance(1);
-			let right = self.parse_unary()?;
-			let expr = BinaryExpression {
-				left: Box::new(left),
-				right: Box::new(right),
-				operator,
-			};
-			left = Expression::Binary(expr);
-		}
-		Ok(left)
+		self.parse_left_associative(Self::parse_unary, |token| match token {
+			Star => Some(BinaryOp::Multiply),
+			Slash => Some(BinaryOp::Divide),
+			DoubleSlash => Some(BinaryOp::FloorDivide),
+			Percent => Some(BinaryOp::Modulo),
+			_ => None,
+		})
 	}
 
 	fn parse_unary(&mut self) -> ParserResult {
-		let mut expr = None;
-		while let Some(operator) = self.current() {
-			let operator = match operator {
-				Plus => UnaryOp::Plus,
-				Minus => UnaryOp::Minus,
-				_ => break,
-			};
+		let operator = match self.current() {
+			Some(Plus) => UnaryOp::Plus,
+			Some(Minus) => UnaryOp::Minus,
+			Some(Bang) => UnaryOp::BitNot,
+			_ => return self.parse_exponent(),
+		};
+		self.advance(1);
+		let right = self.parse_unary()?;
+		Ok(Expression::Unary(UnaryExpression {
+			operator,
+			right: Box::new(right),
+		}))
+	}
+
+	// `**` binds tighter than unary operators and is right-associative,
+	// so `-2 ** 2` is `-(2 ** 2)` and `2 ** 3 ** 2` is `2 ** (3 ** 2)`
+	fn parse_exponent(&mut self) -> ParserResult {
+		let left = self.parse_assignment()?;
+		if let Some(DoubleStar) = self.current() {
 			self.advance(1);
-			let right = self.parse_assignment()?;
-			expr = Some(UnaryExpression {
-				operator,
+			let right = self.parse_unary()?;
+			Ok(Expression::Binary(BinaryExpression {
+				left: Box::new(left),
 				right: Box::new(right),
-			});
+				operator: BinaryOp::Power,
+			}))
+		} else {
+			Ok(left)
 		}
+	}
 
-		if let Some(expr) = expr {
-			Ok(Expression::Unary(expr))
-		} else {
-			self.parse_assignment()
+	/// The fields of an assignment target `ident.field.field =`, `None` if the
+	/// tokens don't start an assignment.
+	fn assignment_fields(&self) -> Option<Vec<Box<str>>> {
+		let Some(Identifier(_)) = self.current() else {
+			return None;
+		};
+		let mut fields = Vec::new();
+		loop {
+			match (self.at(2 * fields.len() + 1), self.at(2 * fields.len() + 2)) {
+				(Some(Assign), _) => return Some(fields),
+				(Some(Dot), Some(Identifier(field))) => fields.push(Box::to_owned(field)),
+				_ => return None,
+			}
 		}
 	}
 
 	fn parse_assignment(&mut self) -> ParserResult {
-		match (self.current(), self.at(1)) {
-			(Some(Identifier(ident)), Some(Assign)) => {
+		match (self.current(), self.assignment_fields()) {
+			(Some(Identifier(ident)), Some(fields)) => {
 				let ident = Box::to_owned(ident);
-				self.advance(2);
+				self.advance(2 * fields.len() + 2);
 				let expr = self.parse_expression()?;
 				Ok(Expression::Assignment(AssignmentExpression {
 					ident,
+					fields,
 					value: Box::new(expr),
 				}))
 			}
@@ -200,31 +330,460 @@ impl Parser {
 		if let Some(Let) = self.current() {
 			self.advance(1);
 
-			let mutable = if let Some(Mutable) = self.current() {
-				self.advance(1);
-				true
-			} else {
-				false
-			};
-
-			match (self.current(), self.at(1)) {
-				(Some(Identifier(ident)), Some(Assign)) => {
-					let ident = Box::to_owned(ident);
-					self.advance(2);
+			let pattern = self.parse_pattern()?;
+			match self.current() {
+				Some(Assign) => {
+					self.advance(1);
 					let expr = self.parse_expression()?;
 					Ok(Expression::Declaration(DeclarationExpression {
-						ident,
+						pattern,
 						value: Box::new(expr),
-						mutable,
 					}))
 				}
-				(Some(Identifier(_)), Some(other)) => Err(UnexpectedToken(other.to_owned())),
-				(Some(Identifier(_)), None) => Err(UnexpectedEOF),
-				(Some(other), _) => Err(UnexpectedToken(other.to_owned())),
-				(None, _) => Err(UnexpectedEOF),
+				Some(other) => Err(UnexpectedToken(other.to_owned())),
+				None => Err(UnexpectedEOF),
 			}
+		} else if let Some(Struct) = self.current() {
+			self.parse_struct_declaration()
+		} else if let Some(Enum) = self.current() {
+			self.parse_enum_declaration()
 		} else {
-			self.parse_primary()
+			self.parse_call()
+		}
+	}
+
+	fn parse_struct_declaration(&mut self) -> ParserResult {
+		let name = match (self.at(1), self.at(2)) {
+			(Some(Identifier(name)), Some(OpenBrace)) => Box::to_owned(name),
+			(Some(Identifier(_)), None) | (None, _) => return Err(UnexpectedEOF),
+			(Some(Identifier(_)), Some(other)) | (Some(other), _) => {
+				return Err(UnexpectedToken(other.to_owned()))
+			}
+		};
+		self.advance(3);
+		let fields = self.parse_fields(|parser| match parser.current() {
+			Some(Identifier(ty)) => {
+				let ty = Box::to_owned(ty);
+				parser.advance(1);
+				Ok(ty)
+			}
+			Some(other) => Err(UnexpectedToken(other.to_owned())),
+			None => Err(ExpectedCloseBrace),
+		})?;
+		Ok(Expression::StructDeclaration(StructDeclaration {
+			name,
+			fields,
+		}))
+	}
+
+	fn parse_enum_declaration(&mut self) -> ParserResult {
+		let name = match (self.at(1), self.at(2)) {
+			(Some(Identifier(name)), Some(OpenBrace)) => Box::to_owned(name),
+			(Some(Identifier(_)), None) | (None, _) => return Err(UnexpectedEOF),
+			(Some(Identifier(_)), Some(other)) | (Some(other), _) => {
+				return Err(UnexpectedToken(other.to_owned()))
+			}
+		};
+		self.advance(3);
+
+		let mut variants: Vec<(Box<str>, Vec<Box<str>>)> = Vec::new();
+		loop {
+			let variant = match self.current() {
+				Some(CloseBrace) => break,
+				Some(Identifier(variant)) => Box::to_owned(variant),
+				Some(other) => return Err(UnexpectedToken(other.to_owned())),
+				None => return Err(ExpectedCloseBrace),
+			};
+			if variants.iter().any(|(other, _)| *other == variant) {
+				return Err(DuplicateVariant(variant));
+			}
+			self.advance(1);
+
+			let mut payload: Vec<Box<str>> = Vec::new();
+			if let Some(OpenParen) = self.current() {
+				self.advance(1);
+				loop {
+					match self.current() {
+						Some(CloseParen) => break,
+						Some(Identifier(ident)) if payload.contains(ident) => {
+							return Err(DuplicateField(Box::to_owned(ident)))
+						}
+						Some(Identifier(ident)) => payload.push(Box::to_owned(ident)),
+						Some(other) => return Err(UnexpectedToken(other.to_owned())),
+						None => return Err(ExpectedCloseParen),
+					}
+					self.advance(1);
+					match self.current() {
+						Some(Comma) => self.advance(1),
+						Some(CloseParen) => (),
+						Some(other) => return Err(UnexpectedToken(other.to_owned())),
+						None => return Err(ExpectedCloseParen),
+					}
+				}
+				self.advance(1);
+			}
+			variants.push((variant, payload));
+
+			match self.current() {
+				Some(Comma) => self.advance(1),
+				Some(CloseBrace) => (),
+				Some(other) => return Err(UnexpectedToken(other.to_owned())),
+				None => return Err(ExpectedCloseBrace),
+			}
+		}
+		self.advance(1);
+		self.enums.insert(
+			name.clone(),
+			variants
+				.iter()
+				.map(|(variant, _)| variant.clone())
+				.collect(),
+		);
+		Ok(Expression::EnumDeclaration(EnumDeclaration {
+			name,
+			variants,
+		}))
+	}
+
+	/// Parses comma separated `field: value` pairs up to the closing brace,
+	/// which may follow a trailing comma.
+	fn parse_fields<T>(
+		&mut self,
+		value: fn(&mut Self) -> Result<T, ParserError>,
+	) -> Result<Vec<(Box<str>, T)>, ParserError> {
+		let mut fields: Vec<(Box<str>, T)> = Vec::new();
+		loop {
+			let ident = match (self.current(), self.at(1)) {
+				(Some(CloseBrace), _) => {
+					self.advance(1);
+					return Ok(fields);
+				}
+				(Some(Identifier(ident)), Some(Colon)) => Box::to_owned(ident),
+				(Some(Identifier(_)), None) | (None, _) => return Err(ExpectedCloseBrace),
+				(Some(Identifier(_)), Some(other)) | (Some(other), _) => {
+					return Err(UnexpectedToken(other.to_owned()))
+				}
+			};
+			if fields.iter().any(|(field, _)| *field == ident) {
+				return Err(DuplicateField(ident));
+			}
+			self.advance(2);
+			fields.push((ident, value(self)?));
+			match self.current() {
+				Some(Comma) => self.advance(1),
+				Some(CloseBrace) => (),
+				Some(other) => return Err(UnexpectedToken(other.to_owned())),
+				None => return Err(ExpectedCloseBrace),
+			}
+		}
+	}
+
+	/// Parses a name, `~name`, `_`, a literal or a range of literals, a tuple
+	/// or list of patterns, or an enum variant with patterns for its payload.
+	fn parse_pattern(&mut self) -> Result<Pattern, ParserError> {
+		let pattern = match (self.current(), self.at(1)) {
+			(Some(Mutable), Some(Identifier(ident))) => {
+				let ident = Box::to_owned(ident);
+				self.advance(1);
+				Pattern::Identifier(ident, true)
+			}
+			(Some(Identifier(ty)), Some(DoubleColon)) => {
+				let variant = match self.at(2) {
+					Some(Identifier(variant)) => variant.to_owned(),
+					Some(other) => return Err(UnexpectedToken(other.to_owned())),
+					None => return Err(UnexpectedEOF),
+				};
+				let path = VariantPath {
+					ty: ty.to_owned(),
+					variant,
+				};
+				self.advance(3);
+				let payload = match self.current() {
+					Some(OpenParen) => {
+						self.advance(1);
+						self.parse_patterns(|tk| matches!(tk, CloseParen), ExpectedCloseParen)?
+					}
+					_ => Vec::new(),
+				};
+				return Ok(Pattern::Variant(path, payload));
+			}
+			(Some(Identifier(ident)), _) => Pattern::Identifier(Box::to_owned(ident), false),
+			(Some(Unit), _) => Pattern::Wildcard,
+			(Some(OpenParen), _) => {
+				self.advance(1);
+				let items =
+					self.parse_patterns(|tk| matches!(tk, CloseParen), ExpectedCloseParen)?;
+				return Ok(Pattern::Tuple(items));
+			}
+			(Some(OpenBracket), _) => {
+				self.advance(1);
+				let items =
+					self.parse_patterns(|tk| matches!(tk, CloseBracket), ExpectedCloseBracket)?;
+				return Ok(Pattern::List(items));
+			}
+			(
+				Some(
+					LiteralNumber(..) | LiteralString(_) | LiteralChar(_) | LiteralBytes(_) | Minus,
+				),
+				_,
+			) => {
+				let low = self.parse_literal()?;
+				let inclusive = match self.current() {
+					Some(DotDot) => false,
+					Some(DotDotEqual) => true,
+					_ => return Ok(Pattern::Literal(Box::new(low))),
+				};
+				self.advance(1);
+				let high = self.parse_literal()?;
+				return Ok(Pattern::Range(Box::new(low), Box::new(high), inclusive));
+			}
+			(Some(Mutable), Some(other)) => return Err(UnexpectedToken(other.to_owned())),
+			(Some(other), _) => return Err(UnexpectedToken(other.to_owned())),
+			(None, _) => return Err(UnexpectedEOF),
+		};
+		self.advance(1);
+		Ok(pattern)
+	}
+
+	/// Parses comma separated patterns up to the closing token, which may
+	/// follow a trailing comma.
+	fn parse_patterns(
+		&mut self,
+		is_close: fn(&Token) -> bool,
+		unclosed: ParserError,
+	) -> Result<Vec<Pattern>, ParserError> {
+		let mut items = Vec::new();
+		loop {
+			if self.current().is_some_and(is_close) {
+				self.advance(1);
+				return Ok(items);
+			}
+			items.push(self.parse_pattern()?);
+			match self.current() {
+				Some(Comma) => self.advance(1),
+				Some(tk) if is_close(tk) => (),
+				Some(other) => return Err(UnexpectedToken(other.to_owned())),
+				None => return Err(unclosed),
+			}
+		}
+	}
+
+	/// Parses a literal in a pattern, which may be negated.
+	fn parse_literal(&mut self) -> ParserResult {
+		match self.current() {
+			Some(Minus) => {
+				self.advance(1);
+				let right = self.parse_literal()?;
+				Ok(Expression::Unary(UnaryExpression {
+					operator: UnaryOp::Minus,
+					right: Box::new(right),
+				}))
+			}
+			Some(LiteralNumber(..) | LiteralString(_) | LiteralChar(_) | LiteralBytes(_)) => {
+				self.parse_primary()
+			}
+			Some(other) => Err(UnexpectedToken(other.to_owned())),
+			None => Err(UnexpectedEOF),
+		}
+	}
+
+	fn parse_match(&mut self) -> ParserResult {
+		self.advance(1);
+		let value = self.parse_restricted(false)?;
+		match self.current() {
+			Some(OpenBrace) => self.advance(1),
+			Some(other) => return Err(UnexpectedToken(other.to_owned())),
+			None => return Err(UnexpectedEOF),
+		}
+
+		let mut arms = Vec::new();
+		loop {
+			if let Some(CloseBrace) = self.current() {
+				self.advance(1);
+				break;
+			}
+			let pattern = self.parse_pattern()?;
+			let guard = match self.current() {
+				Some(If) => {
+					self.advance(1);
+					Some(self.parse_nested()?)
+				}
+				_ => None,
+			};
+			match self.current() {
+				Some(FatArrow) => self.advance(1),
+				Some(other) => return Err(UnexpectedToken(other.to_owned())),
+				None => return Err(ExpectedCloseBrace),
+			}
+			let body = self.parse_nested()?;
+			arms.push(MatchArm {
+				pattern,
+				guard,
+				body,
+			});
+			match self.current() {
+				Some(Comma) => self.advance(1),
+				Some(CloseBrace) => (),
+				Some(other) => return Err(UnexpectedToken(other.to_owned())),
+				None => return Err(ExpectedCloseBrace),
+			}
+		}
+
+		self.check_exhaustive(&arms);
+		Ok(Expression::Match(MatchExpression {
+			value: Box::new(value),
+			arms,
+		}))
+	}
+
+	fn parse_for(&mut self) -> ParserResult {
+		self.advance(1);
+		let pattern = self.parse_pattern()?;
+		match self.current() {
+			Some(In) => self.advance(1),
+			Some(other) => return Err(UnexpectedToken(other.to_owned())),
+			None => return Err(UnexpectedEOF),
+		}
+		let iterable = self.parse_restricted(false)?;
+
+		self.loops += 1;
+		let body = self.parse_block();
+		self.loops -= 1;
+		Ok(Expression::For(ForExpression {
+			pattern,
+			iterable: Box::new(iterable),
+			body: body?,
+		}))
+	}
+
+	/// Parses `{ expr; expr }`, where the last expression may be followed by a
+	/// semicolon too.
+	fn parse_block(&mut self) -> Result<Vec<Expression>, ParserError> {
+		match self.current() {
+			Some(OpenBrace) => self.advance(1),
+			Some(other) => return Err(UnexpectedToken(other.to_owned())),
+			None => return Err(UnexpectedEOF),
+		}
+		let mut body = Vec::new();
+		loop {
+			if let Some(CloseBrace) = self.current() {
+				self.advance(1);
+				return Ok(body);
+			}
+			body.push(self.parse_nested()?);
+			match self.current() {
+				Some(Semicolon) => self.advance(1),
+				Some(CloseBrace) => (),
+				Some(other) => return Err(UnexpectedToken(other.to_owned())),
+				None => return Err(ExpectedCloseBrace),
+			}
+		}
+	}
+
+	/// Warns about a match whose arms are all variants of a known enum, and
+	/// which leaves out some of them. Guarded arms don't count as covering
+	/// their variant.
+	fn check_exhaustive(&mut self, arms: &[MatchArm]) {
+		let mut ty: Option<&str> = None;
+		let mut covered = Vec::new();
+		for arm in arms {
+			let Pattern::Variant(path, payload) = &arm.pattern else {
+				match (arm.pattern.is_irrefutable(), &arm.guard) {
+					(true, None) => return,
+					(true, Some(_)) => continue,
+					(false, _) => return,
+				}
+			};
+			if ty.is_some_and(|ty| ty != &*path.ty) {
+				return;
+			}
+			ty = Some(&path.ty);
+			if arm.guard.is_none() && payload.iter().all(Pattern::is_irrefutable) {
+				covered.push(&path.variant);
+			}
+		}
+
+		let Some((ty, variants)) = ty.and_then(|ty| self.enums.get_key_value(ty)) else {
+			return;
+		};
+		let missing: Vec<_> = variants
+			.iter()
+			.filter(|variant| !covered.contains(variant))
+			.cloned()
+			.collect();
+		if !missing.is_empty() {
+			self.warnings
+				.push(ParserWarning::NonExhaustiveMatch(ty.clone(), missing));
+		}
+	}
+
+	fn parse_call(&mut self) -> ParserResult {
+		let mut expr = self.parse_primary()?;
+		loop {
+			match self.current() {
+				Some(OpenParen) => {
+					self.advance(1);
+					let args =
+						self.parse_sequence(|tk| matches!(tk, CloseParen), ExpectedCloseParen)?;
+					expr = Expression::Call(CallExpression {
+						callee: Box::new(expr),
+						args,
+					});
+				}
+				Some(OpenBracket) => {
+					self.advance(1);
+					let index = self.parse_nested()?;
+					if !matches!(self.current(), Some(CloseBracket)) {
+						return Err(ExpectedCloseBracket);
+					}
+					self.advance(1);
+					expr = Expression::Index(IndexExpression {
+						object: Box::new(expr),
+						index: Box::new(index),
+					});
+				}
+				Some(Dot) => {
+					let ident = match self.at(1) {
+						Some(Identifier(ident)) => Box::to_owned(ident),
+						// keywords still name members, as in `s.match(pattern)`
+						Some(
+							keyword @ (Let | Struct | Enum | Match | If | For | In | Break
+							| Continue),
+						) => Box::from(keyword.to_string()),
+						Some(other) => return Err(UnexpectedToken(other.to_owned())),
+						None => return Err(UnexpectedEOF),
+					};
+					self.advance(2);
+					expr = Expression::Member(MemberExpression {
+						object: Box::new(expr),
+						ident,
+					});
+				}
+				_ => return Ok(expr),
+			}
+		}
+	}
+
+	/// Parses comma separated expressions up to the closing token, which may
+	/// follow a trailing comma.
+	fn parse_sequence(
+		&mut self,
+		is_close: fn(&Token) -> bool,
+		unclosed: ParserError,
+	) -> Result<Vec<Expression>, ParserError> {
+		let mut items = Vec::new();
+		loop {
+			if self.current().is_some_and(is_close) {
+				self.advance(1);
+				return Ok(items);
+			}
+			items.push(self.parse_nested()?);
+			match self.current() {
+				Some(Comma) => self.advance(1),
+				Some(tk) if is_close(tk) => (),
+				Some(other) => return Err(UnexpectedToken(other.to_owned())),
+				None => return Err(unclosed),
+			}
 		}
 	}
 
@@ -232,19 +791,78 @@ impl Parser {
 		let token = self.current().ok_or(UnexpectedEOF)?;
 
 		let next = match token {
-			LiteralNumber(num, true) => Expression::LiteralNumber(Numeric::Float(num.parse()?)),
-			LiteralNumber(num, false) => Expression::LiteralNumber(Numeric::Int(num.parse()?)),
+			LiteralNumber(num, kind) => {
+				let number = Self::parse_number(num, *kind)?;
+				// a unit symbol right after the literal, as in `5 km`
+				let unit = match self.at(1) {
+					Some(Identifier(symbol)) => unit::lookup(symbol),
+					_ => None,
+				};
+				match unit {
+					Some(unit) => {
+						let number = unit
+							.apply(number)
+							.map_err(|_| InvalidNumber(num.to_owned()))?;
+						self.advance(1);
+						Expression::LiteralNumber(number)
+					}
+					None => Expression::LiteralNumber(number),
+				}
+			}
 			LiteralString(st) => Expression::LiteralString(st.to_owned()),
+			LiteralChar(ch) => Expression::LiteralChar(*ch),
+			LiteralBytes(bytes) => Expression::LiteralBytes(bytes.to_owned()),
+			InterpolatedString(parts) => Self::parse_interpolation(parts)?,
+			Identifier(name) if self.struct_literals && matches!(self.at(1), Some(OpenBrace)) => {
+				let name = name.to_owned();
+				self.advance(2);
+				let fields = self.parse_fields(Self::parse_expression)?;
+				return Ok(Expression::StructLiteral(StructLiteral { name, fields }));
+			}
+			Identifier(ty) if matches!(self.at(1), Some(DoubleColon)) => {
+				let variant = match self.at(2) {
+					Some(Identifier(variant)) => variant.to_owned(),
+					Some(other) => return Err(UnexpectedToken(other.to_owned())),
+					None => return Err(UnexpectedEOF),
+				};
+				let ty = ty.to_owned();
+				self.advance(2);
+				Expression::VariantPath(VariantPath { ty, variant })
+			}
 			Identifier(ident) => Expression::Identifier(ident.to_owned()),
+			// a comma makes a tuple, as in `()`, `(1,)` or `(1, 2)`
 			OpenParen => {
 				self.advance(1);
-				let expr = self.parse_expression()?;
+				if let Some(CloseParen) = self.current() {
+					self.advance(1);
+					return Ok(Expression::Tuple(Vec::new()));
+				}
+				let expr = self.parse_nested()?;
+				if let Some(Comma) = self.current() {
+					self.advance(1);
+					let mut items = vec![expr];
+					items.extend(
+						self.parse_sequence(|tk| matches!(tk, CloseParen), ExpectedCloseParen)?,
+					);
+					return Ok(Expression::Tuple(items));
+				}
 				if matches!(self.current(), Some(CloseParen)).not() {
 					return Err(ExpectedCloseParen);
 				}
 				expr
 			}
+			OpenBracket => {
+				self.advance(1);
+				let items =
+					self.parse_sequence(|tk| matches!(tk, CloseBracket), ExpectedCloseBracket)?;
+				return Ok(Expression::List(items));
+			}
 			Unit => Expression::Unit,
+			Match => return self.parse_match(),
+			For => return self.parse_for(),
+			Break if self.loops > 0 => Expression::Break,
+			Continue if self.loops > 0 => Expression::Continue,
+			tk @ (Break | Continue) => return Err(OutsideLoop(tk.to_owned())),
 			unexpected => return Err(UnexpectedToken(unexpected.to_owned())),
 		};
 
@@ -252,6 +870,43 @@ impl Parser {
 		Ok(next)
 	}
 
+	/// Every embedded expression is parsed on its own and has to span all of
+	/// its tokens.
+	fn parse_interpolation(parts: &[StringPart]) -> ParserResult {
+		let parts = parts
+			.iter()
+			.map(|part| match part {
+				StringPart::Text(text) => Ok(InterpolationPart::Text(text.to_owned())),
+				StringPart::Code(tokens) => {
+					let mut parser = Parser {
+						idx: 0,
+						tokens: tokens.to_owned(),
+						..Parser::new()
+					};
+					let expr = parser.parse_expression()?;
+					match parser.current() {
+						None => Ok(InterpolationPart::Expression(expr)),
+						Some(tk) => Err(UnexpectedToken(tk.to_owned())),
+					}
+				}
+			})
+			.collect::<Result<_, ParserError>>()?;
+		Ok(Expression::Interpolation(InterpolationExpression { parts }))
+	}
+
+	fn parse_number(num: &str, kind: NumberKind) -> Result<Numeric, ParserError> {
+		match kind {
+			NumberKind::Float => Ok(Numeric::Float(num.parse()?)),
+			NumberKind::Int(radix) => Ok(Numeric::from(BigInt::from_str_radix(num, radix)?)),
+			NumberKind::Rational => {
+				Numeric::parse_rational(num).ok_or_else(|| InvalidNumber(num.into()))
+			}
+			NumberKind::Decimal => {
+				Numeric::parse_decimal(num).ok_or_else(|| InvalidNumber(num.into()))
+			}
+		}
+	}
+
 	fn clear(&mut self, keep_tokens: bool) {
 		if keep_tokens {
 			self.idx = self.tokens.len();
@@ -261,3 +916,72 @@ impl Parser {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::Parser;
+
+	/// The warnings about `source`, which has to parse.
+	fn warnings(source: &str) -> Vec<String> {
+		let mut parser = Parser::new();
+		parser.produce_ast(source, false).unwrap();
+		parser
+			.take_warnings()
+			.iter()
+			.map(|warning| warning.to_string())
+			.collect()
+	}
+
+	const SHAPE: &str = "enum Shape { Circle(r), Square(s), Empty };";
+
+	#[test]
+	fn matches_leaving_out_variants_warn() {
+		let source = format!("{SHAPE} match Shape::Empty {{ Shape::Circle(r) => r }}");
+		assert_eq!(
+			warnings(&source),
+			["Match on Shape doesn't cover Shape::Square, Shape::Empty"]
+		);
+	}
+
+	#[test]
+	fn guarded_and_refutable_arms_cover_nothing() {
+		let source = format!(
+			"{SHAPE} match Shape::Empty {{ \
+			Shape::Circle(r) if r > 1 => r, Shape::Square(1) => 1, Shape::Empty => 0 }}"
+		);
+		assert_eq!(
+			warnings(&source),
+			["Match on Shape doesn't cover Shape::Circle, Shape::Square"]
+		);
+	}
+
+	#[test]
+	fn exhaustive_matches_dont_warn() {
+		for arms in [
+			"Shape::Circle(r) => r, Shape::Square(_) => 1, Shape::Empty => 0",
+			"Shape::Circle(r) => r, _ => 0",
+			"Shape::Circle(r) => r, other => 0",
+		] {
+			let source = format!("{SHAPE} match Shape::Empty {{ {arms} }}");
+			assert!(warnings(&source).is_empty(), "{arms}");
+		}
+		assert!(warnings("match 1 { 1 => 1 }").is_empty());
+	}
+
+	#[test]
+	fn keywords_name_members() {
+		let mut parser = Parser::new();
+		assert!(parser.produce_ast(r#""abc".match("b")"#, false).is_ok());
+		assert!(parser.produce_ast("x.let", false).is_ok());
+	}
+
+	#[test]
+	fn break_and_continue_need_a_loop() {
+		let mut parser = Parser::new();
+		assert!(parser.produce_ast("break", false).is_err());
+		assert!(parser
+			.produce_ast("match 1 { _ => continue }", false)
+			.is_err());
+		assert!(parser.produce_ast("for i in 0..3 { break }", false).is_ok());
+	}
+}
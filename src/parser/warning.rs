@@ -0,0 +1,25 @@
+use std::fmt::Display;
+
+/// Problems that don't stop the program from running.
+#[derive(Debug, Clone)]
+pub enum ParserWarning {
+	/// A match on an enum whose arms leave out some of its variants.
+	NonExhaustiveMatch(Box<str>, Vec<Box<str>>),
+}
+
+impl Display for ParserWarning {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		use ParserWarning::*;
+
+		match self {
+			NonExhaustiveMatch(ty, missing) => {
+				let missing = missing
+					.iter()
+					.map(|variant| format!("{ty}::{variant}"))
+					.collect::<Box<[_]>>()
+					.join(", ");
+				write!(f, "Match on {ty} doesn't cover {missing}")
+			}
+		}
+	}
+}
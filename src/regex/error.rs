@@ -0,0 +1,41 @@
+use std::fmt::Display;
+
+/// Columns count chars of the pattern, starting at 1.
+#[derive(Debug, Clone)]
+pub enum RegexError {
+	UnclosedGroup(usize),
+	UnopenedGroup(usize),
+	UnclosedClass(usize),
+	InvalidRange(char, char, usize),
+	NothingToRepeat(usize),
+	InvalidRepetition(usize),
+	InvalidEscape(char, usize),
+	TrailingBackslash(usize),
+	InvalidGroupName(usize),
+	DuplicateGroupName(Box<str>, usize),
+	PatternTooLarge,
+}
+
+impl Display for RegexError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		use RegexError::*;
+
+		match self {
+			UnclosedGroup(column) => write!(f, "Unclosed group at column {column}"),
+			UnopenedGroup(column) => write!(f, "Unopened group at column {column}"),
+			UnclosedClass(column) => write!(f, "Unclosed character class at column {column}"),
+			InvalidRange(from, to, column) => {
+				write!(f, "Invalid range {from}-{to} at column {column}")
+			}
+			NothingToRepeat(column) => write!(f, "Nothing to repeat at column {column}"),
+			InvalidRepetition(column) => write!(f, "Invalid repetition count at column {column}"),
+			InvalidEscape(ch, column) => write!(f, "Invalid escape \\{ch} at column {column}"),
+			TrailingBackslash(column) => write!(f, "Trailing backslash at column {column}"),
+			InvalidGroupName(column) => write!(f, "Invalid group name at column {column}"),
+			DuplicateGroupName(name, column) => {
+				write!(f, "Duplicate group name '{name}' at column {column}")
+			}
+			PatternTooLarge => f.write_str("Pattern is too large"),
+		}
+	}
+}
@@ -0,0 +1,672 @@
+pub mod error;
+mod parser;
+
+use std::{fmt::Display, ops::Range};
+
+use error::RegexError;
+use parser::{is_word, Assertion, Class, Node, PatternParser};
+
+/// Patterns compiling to more instructions are rejected, mostly to stop nested
+/// bounded repetitions from blowing up.
+const MAX_PROGRAM_LEN: usize = 100_000;
+
+/// Most instruction and position pairs the backtracker keeps track of, which
+/// takes 32 MiB. Longer texts are searched by the Pike VM instead.
+const MAX_VISITED: usize = 1 << 28;
+
+#[derive(Debug)]
+enum Inst {
+	Char(char),
+	Any,
+	Class(Class),
+	Assert(Assertion),
+	/// Tries the first target, then the second one.
+	Split(usize, usize),
+	Jump(usize),
+	/// Records the position into a capture slot.
+	Save(usize),
+	Match,
+}
+
+/// The byte ranges of a match and its groups, `None` for groups that didn't
+/// take part in the match. Group 0 is the whole match.
+pub type Captures = Box<[Option<Range<usize>>]>;
+
+enum Job {
+	Step(usize, usize),
+	Restore(usize, Option<usize>),
+}
+
+/// The instruction and position pairs tried during a search, as a bit set by
+/// instruction and byte offset into the text. Clearing only resets the words
+/// set since the last clear, so one set serves all the searches of
+/// `captures_iter`.
+struct Visited {
+	width: usize,
+	words: Vec<u64>,
+	touched: Vec<usize>,
+}
+
+impl Visited {
+	/// `None` if the set would hold more than `MAX_VISITED` pairs.
+	fn new(program: usize, text: &str) -> Option<Self> {
+		let width = text.len() + 1;
+		let pairs = program
+			.checked_mul(width)
+			.filter(|&pairs| pairs <= MAX_VISITED)?;
+		Some(Self {
+			width,
+			words: vec![0; pairs.div_ceil(64)],
+			touched: Vec::new(),
+		})
+	}
+
+	/// Marks a pair as tried, `false` if it already was.
+	fn insert(&mut self, pc: usize, pos: usize) -> bool {
+		let idx = pc * self.width + pos;
+		let (word, bit) = (idx / 64, 1 << (idx % 64));
+		if self.words[word] & bit != 0 {
+			return false;
+		}
+		if self.words[word] == 0 {
+			self.touched.push(word);
+		}
+		self.words[word] |= bit;
+		true
+	}
+
+	fn clear(&mut self) {
+		for word in self.touched.drain(..) {
+			self.words[word] = 0;
+		}
+	}
+}
+
+/// The threads of the Pike VM at one position, in priority order, each with
+/// its capture slots.
+type Threads = Vec<(usize, Box<[Option<usize>]>)>;
+
+/// A compiled regular expression. Matching is leftmost-first like in Perl, by
+/// backtracking that never visits an instruction twice at the same position,
+/// so a search takes time linear in the size of the text times the program.
+/// When the table of visited positions would get too large, a Pike VM that
+/// runs all threads in lockstep finds the same match in bounded memory.
+#[derive(Debug)]
+pub struct Regex {
+	pattern: Box<str>,
+	program: Box<[Inst]>,
+	groups: usize,
+	names: Box<[(Box<str>, usize)]>,
+}
+
+impl PartialEq for Regex {
+	fn eq(&self, other: &Self) -> bool {
+		self.pattern == other.pattern
+	}
+}
+
+impl Display for Regex {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.pattern)
+	}
+}
+
+impl Regex {
+	/// Supports classes, the `\d`, `\w` and `\s` shorthands, the `^`, `$` and
+	/// `\b` anchors, alternation, greedy and lazy quantifiers, and capturing,
+	/// non-capturing and named groups.
+	pub fn new(pattern: &str) -> Result<Self, RegexError> {
+		let mut parser = PatternParser::new(pattern);
+		let node = parser.parse()?;
+
+		let mut program = vec![Inst::Save(0)];
+		Self::compile(&node, &mut program)?;
+		program.extend([Inst::Save(1), Inst::Match]);
+		Ok(Self {
+			pattern: Box::from(pattern),
+			program: program.into_boxed_slice(),
+			groups: parser.groups,
+			names: parser.names.into_boxed_slice(),
+		})
+	}
+
+	fn compile(node: &Node, program: &mut Vec<Inst>) -> Result<(), RegexError> {
+		if program.len() > MAX_PROGRAM_LEN {
+			return Err(RegexError::PatternTooLarge);
+		}
+		match node {
+			Node::Empty => (),
+			Node::Literal(ch) => program.push(Inst::Char(*ch)),
+			Node::Any => program.push(Inst::Any),
+			Node::Class(class) => program.push(Inst::Class(class.to_owned())),
+			Node::Assert(assertion) => program.push(Inst::Assert(*assertion)),
+			Node::Group(inner, Some(index)) => {
+				program.push(Inst::Save(2 * index));
+				Self::compile(inner, program)?;
+				program.push(Inst::Save(2 * index + 1));
+			}
+			Node::Group(inner, None) => Self::compile(inner, program)?,
+			Node::Concat(nodes) => {
+				for node in nodes {
+					Self::compile(node, program)?;
+				}
+			}
+			Node::Alternate(branches) => {
+				let mut jumps = Vec::new();
+				for (idx, branch) in branches.iter().enumerate() {
+					if idx == branches.len() - 1 {
+						Self::compile(branch, program)?;
+						break;
+					}
+					let split = program.len();
+					program.push(Inst::Split(split + 1, 0));
+					Self::compile(branch, program)?;
+					jumps.push(program.len());
+					program.push(Inst::Jump(0));
+					program[split] = Inst::Split(split + 1, program.len());
+				}
+				let end = program.len();
+				for jump in jumps {
+					program[jump] = Inst::Jump(end);
+				}
+			}
+			Node::Repeat {
+				node,
+				min,
+				max,
+				greedy,
+			} => {
+				let split = |body: usize, out: usize| match greedy {
+					true => Inst::Split(body, out),
+					false => Inst::Split(out, body),
+				};
+				for _ in 0..*min {
+					Self::compile(node, program)?;
+				}
+				match max {
+					None => {
+						let start = program.len();
+						program.push(Inst::Jump(0));
+						Self::compile(node, program)?;
+						program.push(Inst::Jump(start));
+						program[start] = split(start + 1, program.len());
+					}
+					Some(max) => {
+						let mut splits = Vec::new();
+						for _ in *min..*max {
+							splits.push(program.len());
+							program.push(Inst::Jump(0));
+							Self::compile(node, program)?;
+						}
+						let end = program.len();
+						for start in splits {
+							program[start] = split(start + 1, end);
+						}
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
+	pub fn pattern(&self) -> &str {
+		&self.pattern
+	}
+
+	/// The number of groups, including the whole match.
+	pub fn group_count(&self) -> usize {
+		self.groups
+	}
+
+	pub fn group_index(&self, name: &str) -> Option<usize> {
+		self.names
+			.iter()
+			.find(|(other, _)| **other == *name)
+			.map(|(_, index)| *index)
+	}
+
+	/// The first match starting at or after the byte offset `start`.
+	pub fn captures_at(&self, text: &str, start: usize) -> Option<Captures> {
+		let mut visited = Visited::new(self.program.len(), text);
+		self.search(text, start, visited.as_mut())
+	}
+
+	/// Backtracks if there is a table of visited positions, otherwise runs
+	/// the Pike VM.
+	fn search(&self, text: &str, start: usize, visited: Option<&mut Visited>) -> Option<Captures> {
+		let Some(visited) = visited else {
+			return self.pike_search(text, start);
+		};
+		visited.clear();
+		for begin in (start..=text.len()).filter(|&idx| text.is_char_boundary(idx)) {
+			let mut slots = vec![None; 2 * self.groups];
+			if self.run(text, begin, &mut slots, visited) {
+				return Some(Self::captures(&slots));
+			}
+		}
+		None
+	}
+
+	fn captures(slots: &[Option<usize>]) -> Captures {
+		slots
+			.chunks(2)
+			.map(|slot| match slot {
+				[Some(from), Some(to)] => Some(*from..*to),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// All non-overlapping matches from left to right. An empty match moves the
+	/// search on by one char.
+	pub fn captures_iter<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Captures> + 'a {
+		let mut start = Some(0);
+		let mut visited = Visited::new(self.program.len(), text);
+		std::iter::from_fn(move || {
+			let captures = self.search(text, start.take()?, visited.as_mut())?;
+			let whole = captures[0].clone()?;
+			start = match whole.is_empty() {
+				true => text[whole.end..]
+					.chars()
+					.next()
+					.map(|ch| whole.end + ch.len_utf8()),
+				false => Some(whole.end),
+			};
+			Some(captures)
+		})
+	}
+
+	pub fn is_match(&self, text: &str) -> bool {
+		self.captures_at(text, 0).is_some()
+	}
+
+	/// Replaces all matches with `replacement`, where `$1` or `${1}` stand for
+	/// a group by index, `${name}` for a named group and `$$` for a dollar
+	/// sign. `None` if the replacement refers to a group that doesn't exist.
+	pub fn replace_all(&self, text: &str, replacement: &str) -> Option<String> {
+		let mut result = String::new();
+		let mut last = 0;
+		for captures in self.captures_iter(text) {
+			let whole = captures[0].clone()?;
+			result.push_str(&text[last..whole.start]);
+			self.expand(replacement, text, &captures, &mut result)?;
+			last = whole.end;
+		}
+		result.push_str(&text[last..]);
+		Some(result)
+	}
+
+	fn expand(
+		&self,
+		replacement: &str,
+		text: &str,
+		captures: &Captures,
+		result: &mut String,
+	) -> Option<()> {
+		let mut chars = replacement.chars().peekable();
+		while let Some(ch) = chars.next() {
+			if ch != '$' {
+				result.push(ch);
+				continue;
+			}
+			let reference: String = if chars.next_if_eq(&'{').is_some() {
+				let reference = chars.by_ref().take_while(|&ch| ch != '}').collect();
+				reference
+			} else if chars.next_if_eq(&'$').is_some() {
+				result.push('$');
+				continue;
+			} else {
+				std::iter::from_fn(|| chars.next_if(char::is_ascii_digit)).collect()
+			};
+			if reference.is_empty() {
+				result.push('$');
+				continue;
+			}
+			let index = match reference.parse::<usize>() {
+				Ok(index) => index,
+				Err(_) => self.group_index(&reference)?,
+			};
+			if let Some(range) = captures.get(index)? {
+				result.push_str(&text[range.to_owned()]);
+			}
+		}
+		Some(())
+	}
+
+	pub fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+		let mut parts = Vec::new();
+		let mut last = 0;
+		for captures in self.captures_iter(text) {
+			if let Some(whole) = &captures[0] {
+				parts.push(&text[last..whole.start]);
+				last = whole.end;
+			}
+		}
+		parts.push(&text[last..]);
+		parts
+	}
+
+	/// Runs the program from `begin`, recording the positions of groups in
+	/// `slots`. `visited` holds the instruction and position pairs already
+	/// tried, which can't lead to a match the second time either.
+	fn run(
+		&self,
+		text: &str,
+		begin: usize,
+		slots: &mut [Option<usize>],
+		visited: &mut Visited,
+	) -> bool {
+		let mut stack = vec![Job::Step(0, begin)];
+		while let Some(job) = stack.pop() {
+			let (mut pc, mut pos) = match job {
+				Job::Step(pc, pos) => (pc, pos),
+				Job::Restore(slot, value) => {
+					slots[slot] = value;
+					continue;
+				}
+			};
+			loop {
+				if !visited.insert(pc, pos) {
+					break;
+				}
+
+				let next = text[pos..].chars().next();
+				match &self.program[pc] {
+					Inst::Match => return true,
+					Inst::Char(expected) => match next {
+						Some(ch) if ch == *expected => (pc, pos) = (pc + 1, pos + ch.len_utf8()),
+						_ => break,
+					},
+					Inst::Any => match next {
+						Some(ch) if ch != '\n' => (pc, pos) = (pc + 1, pos + ch.len_utf8()),
+						_ => break,
+					},
+					Inst::Class(class) => match next {
+						Some(ch) if class.matches(ch) => (pc, pos) = (pc + 1, pos + ch.len_utf8()),
+						_ => break,
+					},
+					Inst::Assert(assertion) => {
+						if !Self::holds(assertion, text, pos) {
+							break;
+						}
+						pc += 1;
+					}
+					Inst::Split(first, second) => {
+						stack.push(Job::Step(*second, pos));
+						pc = *first;
+					}
+					Inst::Jump(target) => pc = *target,
+					Inst::Save(slot) => {
+						stack.push(Job::Restore(*slot, slots[*slot]));
+						slots[*slot] = Some(pos);
+						pc += 1;
+					}
+				}
+			}
+		}
+		false
+	}
+
+	fn holds(assertion: &Assertion, text: &str, pos: usize) -> bool {
+		let before = text[..pos].chars().next_back();
+		let next = text[pos..].chars().next();
+		let boundary = before.is_some_and(is_word) != next.is_some_and(is_word);
+		match assertion {
+			Assertion::Start => pos == 0,
+			Assertion::End => pos == text.len(),
+			Assertion::WordBoundary => boundary,
+			Assertion::NotWordBoundary => !boundary,
+		}
+	}
+
+	/// Finds the same match as backtracking by stepping all threads through
+	/// the text together, so it only needs memory for one thread per
+	/// instruction. A thread reaching `Match` cuts off the ones of lower
+	/// priority, and new threads stop starting once there is a match.
+	fn pike_search(&self, text: &str, start: usize) -> Option<Captures> {
+		let (mut current, mut next) = (Threads::new(), Threads::new());
+		// one past the position at which each instruction last got a thread
+		let mut added = vec![0; self.program.len()];
+		let mut matched = None;
+		let mut pos = start;
+		loop {
+			if matched.is_none() {
+				let slots = vec![None; 2 * self.groups].into_boxed_slice();
+				self.add_thread(&mut current, &mut added, 0, pos, slots, text);
+			}
+			if current.is_empty() && matched.is_some() {
+				break;
+			}
+
+			let ch = text[pos..].chars().next();
+			for (pc, slots) in current.drain(..) {
+				let advances = match &self.program[pc] {
+					Inst::Match => {
+						matched = Some(slots);
+						break;
+					}
+					Inst::Char(expected) => ch == Some(*expected),
+					Inst::Any => ch.is_some_and(|ch| ch != '\n'),
+					Inst::Class(class) => ch.is_some_and(|ch| class.matches(ch)),
+					_ => false,
+				};
+				if let (true, Some(ch)) = (advances, ch) {
+					let pos = pos + ch.len_utf8();
+					self.add_thread(&mut next, &mut added, pc + 1, pos, slots, text);
+				}
+			}
+
+			let Some(ch) = ch else {
+				break;
+			};
+			pos += ch.len_utf8();
+			std::mem::swap(&mut current, &mut next);
+		}
+		matched.map(|slots| Self::captures(&slots))
+	}
+
+	/// Follows jumps, splits, saves and assertions from `pc` in priority
+	/// order, adding a thread for every instruction that consumes a char or
+	/// matches. Instructions that already got a thread at this position are
+	/// skipped, since that thread has a higher priority.
+	fn add_thread(
+		&self,
+		threads: &mut Threads,
+		added: &mut [usize],
+		pc: usize,
+		pos: usize,
+		slots: Box<[Option<usize>]>,
+		text: &str,
+	) {
+		let mut stack = vec![(pc, slots)];
+		while let Some((mut pc, mut slots)) = stack.pop() {
+			while added[pc] != pos + 1 {
+				added[pc] = pos + 1;
+				match &self.program[pc] {
+					Inst::Split(first, second) => {
+						stack.push((*second, slots.clone()));
+						pc = *first;
+					}
+					Inst::Jump(target) => pc = *target,
+					Inst::Save(slot) => {
+						slots[*slot] = Some(pos);
+						pc += 1;
+					}
+					Inst::Assert(assertion) if Self::holds(assertion, text, pos) => pc += 1,
+					Inst::Assert(_) => break,
+					_ => {
+						threads.push((pc, slots));
+						break;
+					}
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{error::RegexError, Regex, Visited};
+
+	fn regex(pattern: &str) -> Regex {
+		Regex::new(pattern).unwrap()
+	}
+
+	/// The text of every group of the first match.
+	fn groups<'a>(pattern: &str, text: &'a str) -> Option<Vec<Option<&'a str>>> {
+		let captures = regex(pattern).captures_at(text, 0)?;
+		Some(
+			captures
+				.iter()
+				.map(|range| range.clone().map(|range| &text[range]))
+				.collect(),
+		)
+	}
+
+	fn matches<'a>(pattern: &str, text: &'a str) -> Vec<&'a str> {
+		regex(pattern)
+			.captures_iter(text)
+			.map(|captures| &text[captures[0].clone().unwrap()])
+			.collect()
+	}
+
+	#[test]
+	fn leftmost_first() {
+		assert_eq!(groups("a|ab", "ab"), Some(vec![Some("a")]));
+		assert_eq!(groups("ab|a", "ab"), Some(vec![Some("ab")]));
+		assert_eq!(groups("a+", "baaa"), Some(vec![Some("aaa")]));
+		assert_eq!(groups("a+?", "baaa"), Some(vec![Some("a")]));
+		assert_eq!(groups("x", "abc"), None);
+	}
+
+	#[test]
+	fn repetitions() {
+		assert!(regex("^a{3}$").is_match("aaa"));
+		assert!(!regex("^a{3}$").is_match("aa"));
+		assert!(regex("^a{2,}$").is_match("aaaaa"));
+		assert!(regex("^a{1,3}b?$").is_match("aab"));
+		assert!(!regex("^a{1,3}$").is_match("aaaa"));
+		assert!(regex("^(ab)*$").is_match(""));
+	}
+
+	#[test]
+	fn classes_and_assertions() {
+		assert_eq!(matches(r"\d+", "a1b22c333"), ["1", "22", "333"]);
+		assert_eq!(matches(r"\bcat\b", "cat concat cat"), ["cat", "cat"]);
+		assert_eq!(matches("[^a-c ]+", "abcdef cab xyz"), ["def", "xyz"]);
+		assert_eq!(matches(r"\w+", "héllo wörld"), ["héllo", "wörld"]);
+		assert!(regex(r"^\s*$").is_match(" \t"));
+		assert!(!regex("^.$").is_match("\n"));
+	}
+
+	#[test]
+	fn groups_and_names() {
+		let re = regex(r"(?<year>\d{4})-(\d{2})(-(\d{2}))?");
+		assert_eq!(re.group_count(), 5);
+		assert_eq!(re.group_index("year"), Some(1));
+		assert_eq!(
+			groups(r"(?<year>\d{4})-(\d{2})(-(\d{2}))?", "on 2024-05!"),
+			Some(vec![Some("2024-05"), Some("2024"), Some("05"), None, None])
+		);
+		assert_eq!(groups("(?:a)(b)", "ab"), Some(vec![Some("ab"), Some("b")]));
+	}
+
+	#[test]
+	fn empty_matches_move_on_by_one_char() {
+		assert_eq!(matches("a*", "baaé"), ["", "aa", "", ""]);
+		assert_eq!(regex("x*").split("aé"), ["", "a", "é", ""]);
+	}
+
+	#[test]
+	fn replace_all_expands_groups() {
+		let re = regex(r"(?<key>\w+)=(\w+)");
+		assert_eq!(
+			re.replace_all("a=1, b=2", "$2:${key}$$").as_deref(),
+			Some("1:a$, 2:b$")
+		);
+		assert_eq!(re.replace_all("a=1", "$9"), None);
+	}
+
+	#[test]
+	fn catastrophic_patterns_stay_fast() {
+		let text = "a".repeat(5000);
+		assert!(!regex("^(a*)*b$").is_match(&text));
+		assert_eq!(regex("a").captures_iter(&text).count(), 5000);
+	}
+
+	#[test]
+	fn the_pike_vm_finds_the_same_matches() {
+		let cases = [
+			("a|ab", "ab"),
+			("ab|a", "xab"),
+			("a+?", "baaa"),
+			("(a*)*b", "aaab"),
+			("^a{1,3}b?$", "aab"),
+			(r"(?<year>\d{4})-(\d{2})(-(\d{2}))?", "on 2024-05!"),
+			(r"\bcat\b", "concat cat"),
+			("(a|ab)(c|bcd)(d*)", "abcd"),
+			("é+", "aéé"),
+			("x*", "ab"),
+			("x", "abc"),
+		];
+		for (pattern, text) in cases {
+			let re = regex(pattern);
+			for start in [0, 1] {
+				assert_eq!(
+					re.pike_search(text, start),
+					re.captures_at(text, start),
+					"{pattern} on {text} from {start}"
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn long_texts_fall_back_to_the_pike_vm() {
+		let text = "a".repeat(4_000_000);
+		let re = regex("(b{1,1000}){1,5}");
+		assert!(Visited::new(re.program.len(), &text).is_none());
+		assert!(!re.is_match(&text));
+		let text = text + "bb";
+		assert_eq!(
+			groups("(b{1,1000}){1,5}", &text),
+			Some(vec![Some("bb"), Some("bb")])
+		);
+	}
+
+	#[test]
+	fn invalid_patterns() {
+		assert!(matches!(
+			Regex::new("(a"),
+			Err(RegexError::UnclosedGroup(1))
+		));
+		assert!(matches!(
+			Regex::new("a)"),
+			Err(RegexError::UnopenedGroup(2))
+		));
+		assert!(matches!(
+			Regex::new("[a"),
+			Err(RegexError::UnclosedClass(1))
+		));
+		assert!(matches!(
+			Regex::new("[z-a]"),
+			Err(RegexError::InvalidRange('z', 'a', _))
+		));
+		assert!(matches!(
+			Regex::new("*a"),
+			Err(RegexError::NothingToRepeat(1))
+		));
+		assert!(matches!(
+			Regex::new(r"a\"),
+			Err(RegexError::TrailingBackslash(_))
+		));
+		assert!(matches!(
+			Regex::new("(a{1000}){1000}"),
+			Err(RegexError::PatternTooLarge)
+		));
+		assert!(matches!(
+			Regex::new("(?<x>a)(?<x>b)"),
+			Err(RegexError::DuplicateGroupName(..))
+		));
+	}
+}
@@ -0,0 +1,365 @@
+use super::error::RegexError;
+
+/// Bounded repetitions are expanded when compiling, so their counts are
+/// limited.
+const MAX_REPETITION: u32 = 1000;
+
+#[derive(Debug, Clone, Copy)]
+pub(super) enum PerlClass {
+	Digit,
+	Word,
+	Space,
+}
+
+impl PerlClass {
+	pub(super) fn matches(self, ch: char) -> bool {
+		match self {
+			PerlClass::Digit => ch.is_ascii_digit(),
+			PerlClass::Word => is_word(ch),
+			PerlClass::Space => ch.is_whitespace(),
+		}
+	}
+}
+
+pub(super) fn is_word(ch: char) -> bool {
+	ch.is_alphanumeric() || ch == '_'
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) enum ClassItem {
+	Range(char, char),
+	/// A `\d`, `\w` or `\s` class, negated for `\D`, `\W` and `\S`.
+	Perl(PerlClass, bool),
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Class {
+	negated: bool,
+	items: Vec<ClassItem>,
+}
+
+impl Class {
+	fn perl(class: PerlClass, negated: bool) -> Self {
+		Self {
+			negated: false,
+			items: vec![ClassItem::Perl(class, negated)],
+		}
+	}
+
+	pub(super) fn matches(&self, ch: char) -> bool {
+		let found = self.items.iter().any(|item| match *item {
+			ClassItem::Range(from, to) => (from..=to).contains(&ch),
+			ClassItem::Perl(class, negated) => class.matches(ch) != negated,
+		});
+		found != self.negated
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Assertion {
+	Start,
+	End,
+	WordBoundary,
+	NotWordBoundary,
+}
+
+#[derive(Debug)]
+pub(super) enum Node {
+	Empty,
+	Literal(char),
+	/// `.`, which matches anything but a newline.
+	Any,
+	Class(Class),
+	Assert(Assertion),
+	/// A group with its capture index, `None` for `(?:...)`.
+	Group(Box<Node>, Option<usize>),
+	Concat(Vec<Node>),
+	Alternate(Vec<Node>),
+	Repeat {
+		node: Box<Node>,
+		min: u32,
+		max: Option<u32>,
+		greedy: bool,
+	},
+}
+
+/// Parses a pattern into a syntax tree, counting its capture groups.
+pub(super) struct PatternParser {
+	chars: Vec<char>,
+	idx: usize,
+	pub(super) groups: usize,
+	pub(super) names: Vec<(Box<str>, usize)>,
+}
+
+impl PatternParser {
+	pub(super) fn new(pattern: &str) -> Self {
+		Self {
+			chars: pattern.chars().collect(),
+			idx: 0,
+			groups: 1,
+			names: Vec::new(),
+		}
+	}
+
+	fn current(&self) -> Option<char> {
+		self.chars.get(self.idx).copied()
+	}
+
+	fn eat(&mut self, ch: char) -> bool {
+		let found = self.current() == Some(ch);
+		if found {
+			self.idx += 1;
+		}
+		found
+	}
+
+	fn column(&self) -> usize {
+		self.idx + 1
+	}
+
+	pub(super) fn parse(&mut self) -> Result<Node, RegexError> {
+		let node = self.parse_alternation()?;
+		match self.current() {
+			Some(_) => Err(RegexError::UnopenedGroup(self.column())),
+			None => Ok(node),
+		}
+	}
+
+	fn parse_alternation(&mut self) -> Result<Node, RegexError> {
+		let mut branches = vec![self.parse_concat()?];
+		while self.eat('|') {
+			branches.push(self.parse_concat()?);
+		}
+		Ok(match branches.len() {
+			1 => branches.pop().unwrap_or(Node::Empty),
+			_ => Node::Alternate(branches),
+		})
+	}
+
+	fn parse_concat(&mut self) -> Result<Node, RegexError> {
+		let mut nodes = Vec::new();
+		while let Some(ch) = self.current() {
+			if matches!(ch, '|' | ')') {
+				break;
+			}
+			let atom = self.parse_atom()?;
+			nodes.push(self.parse_quantifier(atom)?);
+		}
+		Ok(match nodes.len() {
+			0 => Node::Empty,
+			1 => nodes.pop().unwrap_or(Node::Empty),
+			_ => Node::Concat(nodes),
+		})
+	}
+
+	fn parse_atom(&mut self) -> Result<Node, RegexError> {
+		let column = self.column();
+		let Some(ch) = self.current() else {
+			return Ok(Node::Empty);
+		};
+		self.idx += 1;
+		Ok(match ch {
+			'.' => Node::Any,
+			'^' => Node::Assert(Assertion::Start),
+			'$' => Node::Assert(Assertion::End),
+			'(' => self.parse_group(column)?,
+			'[' => Node::Class(self.parse_class(column)?),
+			'\\' => self.parse_escape()?,
+			'*' | '+' | '?' => return Err(RegexError::NothingToRepeat(column)),
+			'{' if self.repetition().is_some() => return Err(RegexError::NothingToRepeat(column)),
+			other => Node::Literal(other),
+		})
+	}
+
+	/// Parses the rest of a group, after its opening parenthesis.
+	fn parse_group(&mut self, column: usize) -> Result<Node, RegexError> {
+		let index = if self.eat('?') {
+			if self.eat(':') {
+				None
+			} else if self.eat('<') || (self.eat('P') && self.eat('<')) {
+				Some(self.parse_group_name()?)
+			} else {
+				return Err(RegexError::InvalidGroupName(self.column()));
+			}
+		} else {
+			self.groups += 1;
+			Some(self.groups - 1)
+		};
+		let inner = self.parse_alternation()?;
+		if !self.eat(')') {
+			return Err(RegexError::UnclosedGroup(column));
+		}
+		Ok(Node::Group(Box::new(inner), index))
+	}
+
+	fn parse_group_name(&mut self) -> Result<usize, RegexError> {
+		let column = self.column();
+		let mut name = String::new();
+		while let Some(ch) = self.current().filter(|&ch| is_word(ch)) {
+			name.push(ch);
+			self.idx += 1;
+		}
+		if name.is_empty() || name.starts_with(|ch: char| ch.is_ascii_digit()) || !self.eat('>') {
+			return Err(RegexError::InvalidGroupName(column));
+		}
+		if self.names.iter().any(|(other, _)| **other == *name) {
+			return Err(RegexError::DuplicateGroupName(Box::from(name), column));
+		}
+		self.groups += 1;
+		self.names.push((Box::from(name), self.groups - 1));
+		Ok(self.groups - 1)
+	}
+
+	/// Parses the rest of a class, after its opening bracket. A `]` right at
+	/// the start is a literal.
+	fn parse_class(&mut self, column: usize) -> Result<Class, RegexError> {
+		let negated = self.eat('^');
+		let mut items = Vec::new();
+		let mut first = true;
+		loop {
+			let item_column = self.column();
+			let from = match self.current() {
+				None => return Err(RegexError::UnclosedClass(column)),
+				Some(']') if !first => {
+					self.idx += 1;
+					break;
+				}
+				Some('\\') => {
+					self.idx += 1;
+					match self.parse_class_escape()? {
+						Ok(ch) => ch,
+						Err(item) => {
+							items.push(item);
+							first = false;
+							continue;
+						}
+					}
+				}
+				Some(ch) => {
+					self.idx += 1;
+					ch
+				}
+			};
+			first = false;
+
+			// a `-` at the end of the class is a literal
+			let is_range = self.current() == Some('-')
+				&& self.chars.get(self.idx + 1).is_some_and(|&ch| ch != ']');
+			if !is_range {
+				items.push(ClassItem::Range(from, from));
+				continue;
+			}
+			self.idx += 1;
+			let to = match self.current() {
+				Some('\\') => {
+					self.idx += 1;
+					match self.parse_class_escape()? {
+						Ok(ch) => ch,
+						Err(_) => return Err(RegexError::InvalidRange(from, '\\', item_column)),
+					}
+				}
+				Some(ch) => {
+					self.idx += 1;
+					ch
+				}
+				None => return Err(RegexError::UnclosedClass(column)),
+			};
+			if from > to {
+				return Err(RegexError::InvalidRange(from, to, item_column));
+			}
+			items.push(ClassItem::Range(from, to));
+		}
+		Ok(Class { negated, items })
+	}
+
+	/// An escaped char in a class, or an `Err` holding a Perl class.
+	fn parse_class_escape(&mut self) -> Result<Result<char, ClassItem>, RegexError> {
+		Ok(match self.parse_escape()? {
+			Node::Literal(ch) => Ok(ch),
+			Node::Class(class) => Err(class.items[0]),
+			// word boundaries make no sense in a class
+			_ => {
+				let escape = self.chars[self.idx - 1];
+				return Err(RegexError::InvalidEscape(escape, self.column() - 2));
+			}
+		})
+	}
+
+	/// Parses the rest of an escape, after its backslash.
+	fn parse_escape(&mut self) -> Result<Node, RegexError> {
+		let column = self.column() - 1;
+		let Some(ch) = self.current() else {
+			return Err(RegexError::TrailingBackslash(column));
+		};
+		self.idx += 1;
+		Ok(match ch {
+			'd' => Node::Class(Class::perl(PerlClass::Digit, false)),
+			'D' => Node::Class(Class::perl(PerlClass::Digit, true)),
+			'w' => Node::Class(Class::perl(PerlClass::Word, false)),
+			'W' => Node::Class(Class::perl(PerlClass::Word, true)),
+			's' => Node::Class(Class::perl(PerlClass::Space, false)),
+			'S' => Node::Class(Class::perl(PerlClass::Space, true)),
+			'b' => Node::Assert(Assertion::WordBoundary),
+			'B' => Node::Assert(Assertion::NotWordBoundary),
+			'n' => Node::Literal('\n'),
+			'r' => Node::Literal('\r'),
+			't' => Node::Literal('\t'),
+			ch if ch.is_ascii_punctuation() => Node::Literal(ch),
+			other => return Err(RegexError::InvalidEscape(other, column)),
+		})
+	}
+
+	/// Reads a `{n}`, `{n,}` or `{n,m}` repetition after its opening brace,
+	/// without consuming it. `None` if it's not one, so the brace is a literal.
+	fn repetition(&self) -> Option<(u32, Option<u32>, usize)> {
+		let rest: String = self.chars[self.idx..]
+			.iter()
+			.take_while(|&&ch| ch != '}')
+			.collect();
+		if self.chars.get(self.idx + rest.chars().count()) != Some(&'}') {
+			return None;
+		}
+		let (min, max) = match rest.split_once(',') {
+			None => (rest.parse().ok()?, Some(rest.parse().ok()?)),
+			Some((min, "")) => (min.parse().ok()?, None),
+			Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+		};
+		Some((min, max, rest.chars().count() + 1))
+	}
+
+	fn parse_quantifier(&mut self, mut node: Node) -> Result<Node, RegexError> {
+		loop {
+			let column = self.column();
+			let (min, max) = match self.current() {
+				Some('*') => (0, None),
+				Some('+') => (1, None),
+				Some('?') => (0, Some(1)),
+				Some('{') => {
+					self.idx += 1;
+					match self.repetition() {
+						Some((min, max, len)) => {
+							self.idx += len - 1;
+							(min, max)
+						}
+						None => {
+							self.idx -= 1;
+							return Ok(node);
+						}
+					}
+				}
+				_ => return Ok(node),
+			};
+			self.idx += 1;
+			if min > MAX_REPETITION || max.is_some_and(|max| max > MAX_REPETITION || max < min) {
+				return Err(RegexError::InvalidRepetition(column));
+			}
+			let greedy = !self.eat('?');
+			node = Node::Repeat {
+				node: Box::new(node),
+				min,
+				max,
+				greedy,
+			};
+		}
+	}
+}
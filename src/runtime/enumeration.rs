@@ -0,0 +1,71 @@
+use std::{fmt::Display, rc::Rc};
+
+use crate::expression::enumeration::write_variants;
+
+use super::value::RuntimeValue;
+
+/// A declared enum. Like structs, every declaration makes a distinct type.
+#[derive(Debug)]
+pub struct EnumType {
+	pub name: Box<str>,
+	pub variants: Vec<(Box<str>, Vec<Box<str>>)>,
+}
+
+impl PartialEq for EnumType {
+	fn eq(&self, other: &Self) -> bool {
+		std::ptr::eq(self, other)
+	}
+}
+
+impl EnumType {
+	pub fn variant_index(&self, ident: &str) -> Option<usize> {
+		self.variants
+			.iter()
+			.position(|(variant, _)| **variant == *ident)
+	}
+
+	/// The full name of a variant, as in `Shape::Circle`.
+	pub fn variant_name(&self, variant: usize) -> String {
+		format!("{}::{}", self.name, self.variants[variant].0)
+	}
+}
+
+impl Display for EnumType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "enum {} {{", self.name)?;
+		write_variants(f, &self.variants)
+	}
+}
+
+/// A variant of an enum with its payload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantValue {
+	pub ty: Rc<EnumType>,
+	pub variant: usize,
+	pub payload: Vec<RuntimeValue>,
+}
+
+impl VariantValue {
+	/// The payload item with the name given in the declaration.
+	pub fn field(&self, ident: &str) -> Option<&RuntimeValue> {
+		let (_, names) = &self.ty.variants[self.variant];
+		let idx = names.iter().position(|name| **name == *ident)?;
+		Some(&self.payload[idx])
+	}
+}
+
+impl Display for VariantValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.ty.variant_name(self.variant))?;
+		if self.payload.is_empty() {
+			return Ok(());
+		}
+		let payload = self
+			.payload
+			.iter()
+			.map(|item| item.to_string())
+			.collect::<Box<[_]>>()
+			.join(", ");
+		write!(f, "({payload})")
+	}
+}
@@ -1,6 +1,10 @@
 use std::fmt::Display;
 
-use crate::expression::{binary::BinaryOp, unary::UnaryOp};
+use crate::{
+	expression::{binary::BinaryOp, unary::UnaryOp},
+	numeric::{error::NumericError, unit::Dimension},
+	regex::error::RegexError,
+};
 
 use super::value::RuntimeValue;
 
@@ -27,6 +31,50 @@ pub enum RuntimeError {
 	VariableNotDeclared(Box<str>),
 	VariableTypeDoesntMatch(Box<str>),
 	CannotMutateVariable(Box<str>),
+	Arithmetic(NumericError),
+	NotCallable(RuntimeValue),
+	WrongArgumentCount(Box<str>, usize, usize),
+	TooFewArguments(Box<str>, usize, usize),
+	InvalidArgument(Box<str>, RuntimeValue),
+	UnknownMode(Box<str>, Box<str>, &'static [&'static str]),
+	NoSuchMember(RuntimeValue, Box<str>),
+	DimensionMismatch(Dimension, Dimension),
+	NotIndexable(RuntimeValue),
+	InvalidIndex(RuntimeValue, RuntimeValue),
+	InvalidPlaceholder(Box<str>, usize),
+	InvalidPattern(RegexError),
+	CannotDestructure(RuntimeValue, usize),
+	UnknownType(Box<str>),
+	NotAStruct(RuntimeValue),
+	NotAnEnum(RuntimeValue),
+	MissingField(Box<str>, Box<str>),
+	FieldTypeMismatch(Box<str>, Box<str>, RuntimeValue),
+	NoMatchingArm(RuntimeValue),
+	PatternMismatch(Box<str>, RuntimeValue),
+	PayloadCountMismatch(Box<str>, usize, usize),
+	NotACondition(RuntimeValue),
+	NotIterable(RuntimeValue),
+	InvalidRangeBound(RuntimeValue),
+	ZeroStep,
+	/// Leaves the innermost loop, which is the only place the parser allows it.
+	Break,
+	/// Goes on with the next iteration of the innermost loop.
+	Continue,
+}
+
+impl From<NumericError> for RuntimeError {
+	fn from(value: NumericError) -> Self {
+		match value {
+			NumericError::DimensionMismatch(left, right) => Self::DimensionMismatch(left, right),
+			other => Self::Arithmetic(other),
+		}
+	}
+}
+
+impl From<RegexError> for RuntimeError {
+	fn from(value: RegexError) -> Self {
+		Self::InvalidPattern(value)
+	}
 }
 
 impl Display for RuntimeError {
@@ -40,6 +88,80 @@ impl Display for RuntimeError {
 				write!(f, "Variable '{ident}' is of a different type")
 			}
 			CannotMutateVariable(ident) => write!(f, "Cannot mutate immutable variable '{ident}'"),
+			Arithmetic(numeric_error) => Display::fmt(numeric_error, f),
+			NotCallable(value) => write!(f, "Value {value} is not callable"),
+			WrongArgumentCount(name, expected, got) => {
+				write!(
+					f,
+					"Function '{name}' expects {expected} arguments, got {got}"
+				)
+			}
+			TooFewArguments(name, minimum, got) => {
+				let plural = if *minimum == 1 { "" } else { "s" };
+				write!(
+					f,
+					"Function '{name}' expects at least {minimum} argument{plural}, got {got}"
+				)
+			}
+			InvalidArgument(name, value) => {
+				write!(f, "Invalid argument for function '{name}': {value}")
+			}
+			UnknownMode(name, mode, modes) => {
+				let modes = modes
+					.iter()
+					.map(|mode| format!("{mode:?}"))
+					.collect::<Box<[_]>>()
+					.join(", ");
+				write!(
+					f,
+					"Unknown mode {mode:?} for function '{name}', expected one of {modes}"
+				)
+			}
+			NoSuchMember(value, ident) => write!(f, "Value {value} has no member '{ident}'"),
+			DimensionMismatch(left, right) => write!(f, "Units don't match: {left} and {right}"),
+			NotIndexable(value) => write!(f, "Value {value} can't be indexed"),
+			InvalidIndex(value, index) => write!(f, "Cannot index {value} with {index}"),
+			InvalidPlaceholder(placeholder, column) => {
+				write!(
+					f,
+					"Invalid placeholder '{placeholder}' at column {column} of format string"
+				)
+			}
+			InvalidPattern(regex_error) => write!(f, "Invalid pattern: {regex_error}"),
+			CannotDestructure(value, arity) => {
+				write!(
+					f,
+					"Cannot destructure {value} into a tuple of {arity} items"
+				)
+			}
+			UnknownType(ident) => write!(f, "Unknown type '{ident}'"),
+			NotAStruct(value) => write!(f, "Value {value} is not a struct type"),
+			NotAnEnum(value) => write!(f, "Value {value} is not an enum type"),
+			MissingField(name, field) => write!(f, "Missing field '{field}' of struct {name}"),
+			FieldTypeMismatch(field, ty, value) => {
+				write!(
+					f,
+					"Field '{field}' expects a value of type {ty}, got {value}"
+				)
+			}
+			NoMatchingArm(value) => write!(f, "No match arm matches {value}"),
+			PatternMismatch(pattern, value) => {
+				write!(f, "Value {value} doesn't match pattern {pattern}")
+			}
+			PayloadCountMismatch(variant, expected, got) => {
+				write!(
+					f,
+					"Variant '{variant}' has {expected} payload items, pattern gives {got}"
+				)
+			}
+			NotACondition(value) => write!(f, "Value {value} is not a condition"),
+			NotIterable(value) => write!(f, "Value {value} can't be iterated"),
+			InvalidRangeBound(value) => {
+				write!(f, "Range bounds and steps must be integers, got {value}")
+			}
+			ZeroStep => f.write_str("Range step can't be zero"),
+			Break => f.write_str("'break' outside of a loop"),
+			Continue => f.write_str("'continue' outside of a loop"),
 		}
 	}
 }
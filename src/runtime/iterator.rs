@@ -0,0 +1,214 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use crate::{environment::Env, numeric::Numeric};
+
+use super::{error::RuntimeError, value::RuntimeValue};
+
+enum Source {
+	/// The items of a range or a collection.
+	Items(Box<dyn Iterator<Item = RuntimeValue>>),
+	Map(IteratorValue, RuntimeValue),
+	Filter(IteratorValue, RuntimeValue),
+	/// Stops after the given number of items, without taking any more from
+	/// its source.
+	Take(IteratorValue, usize),
+	/// Drops the given number of items the first time it is advanced.
+	Skip(IteratorValue, usize),
+	Zip(IteratorValue, IteratorValue),
+	Enumerate(IteratorValue, usize),
+	Chain(IteratorValue, IteratorValue),
+	/// Goes through the iterable the function gives for each item, which is
+	/// the one currently in progress.
+	FlatMap(IteratorValue, RuntimeValue, Option<IteratorValue>),
+}
+
+/// A stateful iterator. Copies of the value share their position, so taking
+/// an item through one of them advances all of them. Adapters take items
+/// from their source one at a time, so nothing is collected on the way.
+#[derive(Clone)]
+pub struct IteratorValue {
+	source: Rc<RefCell<Source>>,
+}
+
+impl std::fmt::Debug for IteratorValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("IteratorValue")
+	}
+}
+
+impl PartialEq for IteratorValue {
+	fn eq(&self, other: &Self) -> bool {
+		Rc::ptr_eq(&self.source, &other.source)
+	}
+}
+
+impl Display for IteratorValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("iterator")
+	}
+}
+
+impl IteratorValue {
+	fn new(source: Source) -> Self {
+		Self {
+			source: Rc::new(RefCell::new(source)),
+		}
+	}
+
+	pub fn items(items: impl Iterator<Item = RuntimeValue> + 'static) -> Self {
+		Self::new(Source::Items(Box::new(items)))
+	}
+
+	pub fn map(self, func: RuntimeValue) -> Self {
+		Self::new(Source::Map(self, func))
+	}
+
+	pub fn filter(self, func: RuntimeValue) -> Self {
+		Self::new(Source::Filter(self, func))
+	}
+
+	pub fn take(self, count: usize) -> Self {
+		Self::new(Source::Take(self, count))
+	}
+
+	pub fn skip(self, count: usize) -> Self {
+		Self::new(Source::Skip(self, count))
+	}
+
+	pub fn zip(self, other: Self) -> Self {
+		Self::new(Source::Zip(self, other))
+	}
+
+	pub fn enumerate(self) -> Self {
+		Self::new(Source::Enumerate(self, 0))
+	}
+
+	pub fn chain(self, other: Self) -> Self {
+		Self::new(Source::Chain(self, other))
+	}
+
+	pub fn flat_map(self, func: RuntimeValue) -> Self {
+		Self::new(Source::FlatMap(self, func, None))
+	}
+
+	/// The next item, `None` once the iterator is exhausted. Functions given
+	/// to adapters are called in `env`.
+	pub fn next(&self, env: &Env) -> Result<Option<RuntimeValue>, RuntimeError> {
+		let mut source = self.source.borrow_mut();
+		match &mut *source {
+			Source::Items(items) => Ok(items.next()),
+			Source::Map(inner, func) => match inner.next(env)? {
+				Some(item) => Ok(Some(func.call(env, vec![item])?)),
+				None => Ok(None),
+			},
+			Source::Filter(inner, func) => {
+				while let Some(item) = inner.next(env)? {
+					if func.call(env, vec![item.to_owned()])?.is_truthy()? {
+						return Ok(Some(item));
+					}
+				}
+				Ok(None)
+			}
+			Source::Take(_, 0) => Ok(None),
+			Source::Take(inner, remaining) => {
+				*remaining -= 1;
+				inner.next(env)
+			}
+			Source::Skip(inner, count) => {
+				for _ in 0..std::mem::take(count) {
+					if inner.next(env)?.is_none() {
+						return Ok(None);
+					}
+				}
+				inner.next(env)
+			}
+			Source::Zip(left, right) => {
+				let Some(left) = left.next(env)? else {
+					return Ok(None);
+				};
+				let Some(right) = right.next(env)? else {
+					return Ok(None);
+				};
+				Ok(Some(RuntimeValue::Tuple(vec![left, right])))
+			}
+			Source::Enumerate(inner, idx) => {
+				let Some(item) = inner.next(env)? else {
+					return Ok(None);
+				};
+				let pair = vec![RuntimeValue::Number(Numeric::Int(*idx as i128)), item];
+				*idx += 1;
+				Ok(Some(RuntimeValue::Tuple(pair)))
+			}
+			Source::Chain(first, second) => match first.next(env)? {
+				Some(item) => Ok(Some(item)),
+				None => second.next(env),
+			},
+			Source::FlatMap(inner, func, current) => loop {
+				if let Some(items) = current {
+					if let Some(item) = items.next(env)? {
+						return Ok(Some(item));
+					}
+				}
+				let Some(item) = inner.next(env)? else {
+					return Ok(None);
+				};
+				*current = Some(func.call(env, vec![item])?.iterate()?);
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::IteratorValue;
+	use crate::{environment::Env, numeric::Numeric, runtime::value::RuntimeValue};
+
+	fn ints(items: impl IntoIterator<Item = i128> + 'static) -> IteratorValue {
+		IteratorValue::items(
+			items
+				.into_iter()
+				.map(|i| RuntimeValue::Number(Numeric::Int(i))),
+		)
+	}
+
+	fn collect(iterator: &IteratorValue) -> Vec<String> {
+		let env = Env::global();
+		let mut items = Vec::new();
+		while let Some(item) = iterator.next(&env).unwrap() {
+			items.push(item.to_string());
+		}
+		items
+	}
+
+	#[test]
+	fn take_and_skip() {
+		assert_eq!(collect(&ints(0..10).skip(2).take(3)), ["2", "3", "4"]);
+		assert_eq!(collect(&ints(0..3).skip(5)), Vec::<String>::new());
+		// take doesn't pull more items than it gives out
+		let source = ints(0..5);
+		assert_eq!(collect(&source.clone().take(2)), ["0", "1"]);
+		assert_eq!(collect(&source), ["2", "3", "4"]);
+	}
+
+	#[test]
+	fn zip_enumerate_and_chain() {
+		assert_eq!(
+			collect(&ints(0..5).zip(ints(10..12))),
+			["(0, 10)", "(1, 11)"]
+		);
+		assert_eq!(collect(&ints(7..9).enumerate()), ["(0, 7)", "(1, 8)"]);
+		assert_eq!(collect(&ints(0..2).chain(ints(5..6))), ["0", "1", "5"]);
+	}
+
+	#[test]
+	fn copies_share_their_position() {
+		let env = Env::global();
+		let iterator = ints(0..3);
+		let copy = iterator.clone();
+		assert!(copy.next(&env).unwrap().is_some());
+		assert_eq!(collect(&iterator), ["1", "2"]);
+		assert!(copy.next(&env).unwrap().is_none());
+		assert!(iterator == copy);
+		assert!(iterator != ints(0..3));
+	}
+}
@@ -1,21 +1,43 @@
+pub mod enumeration;
 pub mod error;
+pub mod iterator;
+pub mod range;
+pub mod structure;
 pub mod value;
 pub mod variable;
 
 use std::{
+	cmp::Ordering,
 	fmt::Display,
-	ops::{Add, Div, Mul, Neg, Rem, Sub},
+	ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Shl, Shr, Sub},
+	rc::Rc,
 };
 
+use enumeration::{EnumType, VariantValue};
 use error::{RuntimeError, RuntimeOperation};
-use variable::{Pos, RuntimeVariable};
+use num_traits::Pow;
+use range::RangeValue;
+use structure::{FieldType, StructType, StructValue};
+use value::RuntimeValue;
+use variable::{FloorDiv, Modulo, Pos, RuntimeVariable};
 
 use crate::{
+	builtins,
 	environment::Env,
 	expression::{
 		assignment::AssignmentExpression,
 		binary::{BinaryExpression, BinaryOp},
+		call::CallExpression,
 		declaration::DeclarationExpression,
+		enumeration::{EnumDeclaration, VariantPath},
+		index::IndexExpression,
+		interpolation::{InterpolationExpression, InterpolationPart},
+		looping::ForExpression,
+		matching::MatchExpression,
+		member::MemberExpression,
+		pattern::Pattern,
+		range::RangeExpression,
+		structure::{StructDeclaration, StructLiteral},
 		unary::{UnaryExpression, UnaryOp},
 		Expression,
 	},
@@ -47,77 +69,1047 @@ impl Runtime {
 	}
 
 	pub fn evaluate(&self, expr: Expression) -> RuntimeResult {
+		self.evaluate_in(expr, &self.global_env)
+	}
+
+	/// Evaluates `expr` with `env` as the innermost scope.
+	fn evaluate_in(&self, expr: Expression, env: &Env) -> RuntimeResult {
 		use Expression::*;
 
 		match expr {
-			Program(program) => self.evaluate_program(program),
+			Program(program) => self.evaluate_program(program, env),
 			LiteralNumber(number) => Ok(RuntimeVariable::number(number)),
 			LiteralString(string) => Ok(RuntimeVariable::string(string.into_string())),
-			Identifier(ident) => self.global_env.evaluate(&ident),
-			Unary(unary) => self.evaluate_unary(unary),
-			Binary(binary) => self.evaluate_binary(binary),
+			LiteralChar(ch) => Ok(RuntimeVariable::from(RuntimeValue::Char(ch))),
+			LiteralBytes(bytes) => Ok(RuntimeVariable::from(RuntimeValue::Bytes(bytes))),
+			Interpolation(interpolation) => self.evaluate_interpolation(interpolation, env),
+			Identifier(ident) => env.evaluate(&ident),
+			Unary(unary) => self.evaluate_unary(unary, env),
+			Binary(binary) => self.evaluate_binary(binary, env),
 			Unit => Ok(RuntimeVariable::unit()),
-			Assignment(assignment) => self.evaluate_assignment(assignment, &self.global_env),
-			Declaration(declaration) => self.evaluate_declaration(declaration, &self.global_env),
+			Assignment(assignment) => self.evaluate_assignment(assignment, env),
+			Declaration(declaration) => self.evaluate_declaration(declaration, env),
+			Call(call) => self.evaluate_call(call, env),
+			Member(member) => self.evaluate_member(member, env),
+			Index(index) => self.evaluate_index(index, env),
+			List(items) => Ok(RuntimeVariable::from(RuntimeValue::List(
+				self.evaluate_items(items, env)?,
+			))),
+			Tuple(items) => Ok(RuntimeVariable::from(RuntimeValue::Tuple(
+				self.evaluate_items(items, env)?,
+			))),
+			StructDeclaration(declaration) => self.evaluate_struct_declaration(declaration, env),
+			StructLiteral(literal) => self.evaluate_struct_literal(literal, env),
+			EnumDeclaration(declaration) => self.evaluate_enum_declaration(declaration, env),
+			VariantPath(path) => self.evaluate_variant_path(path, env),
+			Match(matching) => self.evaluate_match(matching, env),
+			Range(range) => self.evaluate_range(range, env),
+			For(looping) => self.evaluate_for(looping, env),
+			Break => Err(RuntimeError::Break),
+			Continue => Err(RuntimeError::Continue),
 		}
 	}
 
-	fn evaluate_program(&self, program: Vec<Expression>) -> RuntimeResult {
+	fn evaluate_program(&self, program: Vec<Expression>, env: &Env) -> RuntimeResult {
 		let mut last = RuntimeVariable::unit();
 		for expr in program {
-			last = self.evaluate(expr)?;
+			last = self.evaluate_in(expr, env)?;
 		}
 		Ok(last)
 	}
 
-	fn evaluate_unary(&self, unary: UnaryExpression) -> RuntimeResult {
+	fn evaluate_interpolation(
+		&self,
+		interpolation: InterpolationExpression,
+		env: &Env,
+	) -> RuntimeResult {
+		let mut text = String::new();
+		for part in interpolation.parts {
+			match part {
+				InterpolationPart::Text(part) => text.push_str(&part),
+				InterpolationPart::Expression(expr) => {
+					text.push_str(&self.evaluate_in(expr, env)?.inner().to_text())
+				}
+			}
+		}
+		Ok(RuntimeVariable::string(text))
+	}
+
+	fn evaluate_unary(&self, unary: UnaryExpression, env: &Env) -> RuntimeResult {
 		use UnaryOp::*;
 
-		let right = self.evaluate(*unary.right)?;
+		let right = self.evaluate_in(*unary.right, env)?;
 
 		match unary.operator {
 			Plus => right.pos(),
 			Minus => right.neg(),
+			BitNot => right.not(),
 		}
 	}
 
-	fn evaluate_binary(&self, binary: BinaryExpression) -> RuntimeResult {
+	fn evaluate_binary(&self, binary: BinaryExpression, env: &Env) -> RuntimeResult {
 		use BinaryOp::*;
 		use RuntimeError::*;
 
-		let left = self.evaluate(*binary.left)?;
-		let right = self.evaluate(*binary.right)?;
+		let left = self.evaluate_in(*binary.left, env)?;
+		let right = self.evaluate_in(*binary.right, env)?;
 
 		match binary.operator {
 			Add => left.add(right),
 			Subtract => left.sub(right),
 			Multiply => left.mul(right),
 			Divide => left.div(right),
-			Modulo => left.rem(right),
-			Equals => {
+			FloorDivide => left.floor_div(right),
+			Modulo => left.modulo(right),
+			Power => left.pow(right),
+			BitAnd => left.bitand(right),
+			BitOr => left.bitor(right),
+			BitXor => left.bitxor(right),
+			ShiftLeft => left.shl(right),
+			ShiftRight => left.shr(right),
+			operator @ (Equals | NotEquals) => {
 				if left.same_type(&right) {
+					// only the values count, not whether the variables are mutable
+					let equal = *left.inner() == *right.inner();
 					Ok(RuntimeVariable::number(Numeric::Int(
-						left.eq(&right) as i128
+						(equal != matches!(operator, NotEquals)) as i128,
 					)))
 				} else {
 					Err(UnsupportedOperation(RuntimeOperation::Binary(
 						left.inner().to_owned(),
-						BinaryOp::Equals,
+						operator,
 						right.inner().to_owned(),
 					)))
 				}
 			}
+			Less => Self::evaluate_comparison(left, right, Less, Ordering::is_lt),
+			LessEqual => Self::evaluate_comparison(left, right, LessEqual, Ordering::is_le),
+			Greater => Self::evaluate_comparison(left, right, Greater, Ordering::is_gt),
+			GreaterEqual => Self::evaluate_comparison(left, right, GreaterEqual, Ordering::is_ge),
 		}
 	}
 
+	/// Comparisons involving NaN are always false.
+	fn evaluate_comparison(
+		left: RuntimeVariable,
+		right: RuntimeVariable,
+		operator: BinaryOp,
+		holds: fn(Ordering) -> bool,
+	) -> RuntimeResult {
+		let ordering = left.inner().compare(&right.inner(), operator)?;
+		Ok(RuntimeVariable::number(Numeric::Int(
+			ordering.is_some_and(holds) as i128,
+		)))
+	}
+
 	fn evaluate_assignment(&self, assignment: AssignmentExpression, env: &Env) -> RuntimeResult {
-		self.evaluate(*assignment.value)
-			.and_then(|value| env.assign(&assignment.ident, value))
+		let value = self.evaluate_in(*assignment.value, env)?;
+		if assignment.fields.is_empty() {
+			return env.assign(&assignment.ident, value);
+		}
+
+		// the struct is copied, so other variables holding it don't change
+		let variable = env.evaluate(&assignment.ident)?;
+		if !variable.mutable {
+			return Err(RuntimeError::CannotMutateVariable(assignment.ident));
+		}
+		let mut updated = variable.inner().to_owned();
+		let field = value.inner().to_owned();
+		Self::update_field(&mut updated, &assignment.fields, field)?;
+		env.assign(&assignment.ident, RuntimeVariable::from(updated))?;
+		Ok(value)
+	}
+
+	fn update_field(
+		object: &mut RuntimeValue,
+		fields: &[Box<str>],
+		value: RuntimeValue,
+	) -> Result<(), RuntimeError> {
+		let [ident, rest @ ..] = fields else {
+			*object = value;
+			return Ok(());
+		};
+		let RuntimeValue::Struct(structure) = object else {
+			return Err(RuntimeError::NoSuchMember(
+				object.to_owned(),
+				ident.to_owned(),
+			));
+		};
+		let Some(idx) = structure.ty.field_index(ident) else {
+			return Err(RuntimeError::NoSuchMember(
+				object.to_owned(),
+				ident.to_owned(),
+			));
+		};
+		let ty = Rc::clone(&structure.ty);
+		let field = &mut structure.fields[idx];
+		Self::update_field(field, rest, value)?;
+		Self::check_field(&ty, idx, field)
+	}
+
+	fn check_field(ty: &StructType, idx: usize, value: &RuntimeValue) -> Result<(), RuntimeError> {
+		let (ident, field_type) = &ty.fields[idx];
+		if field_type.matches(value) {
+			Ok(())
+		} else {
+			Err(RuntimeError::FieldTypeMismatch(
+				Box::from(format!("{}.{ident}", ty.name)),
+				Box::from(field_type.to_string()),
+				value.to_owned(),
+			))
+		}
+	}
+
+	fn evaluate_struct_declaration(
+		&self,
+		declaration: StructDeclaration,
+		env: &Env,
+	) -> RuntimeResult {
+		let fields = declaration
+			.fields
+			.into_iter()
+			.map(|(ident, ty)| {
+				let field_type = FieldType::builtin(&ty)
+					.or_else(|| self.named_type(&ty, env))
+					.ok_or(RuntimeError::UnknownType(ty))?;
+				Ok((ident, field_type))
+			})
+			.collect::<Result<_, RuntimeError>>()?;
+		let ty = RuntimeValue::StructType(Rc::new(StructType {
+			name: declaration.name.clone(),
+			fields,
+		}));
+		Ok(env.declare(&declaration.name, RuntimeVariable::from(ty)))
+	}
+
+	/// The struct or enum declared as `name`.
+	fn named_type(&self, name: &str, env: &Env) -> Option<FieldType> {
+		match &*env.evaluate(name).ok()?.inner() {
+			RuntimeValue::StructType(ty) => Some(FieldType::Struct(Rc::clone(ty))),
+			RuntimeValue::EnumType(ty) => Some(FieldType::Enum(Rc::clone(ty))),
+			_ => None,
+		}
 	}
 
+	fn evaluate_enum_declaration(&self, declaration: EnumDeclaration, env: &Env) -> RuntimeResult {
+		let ty = RuntimeValue::EnumType(Rc::new(EnumType {
+			name: declaration.name.clone(),
+			variants: declaration.variants,
+		}));
+		Ok(env.declare(&declaration.name, RuntimeVariable::from(ty)))
+	}
+
+	/// A variant without a payload is a value, others are constructors.
+	fn evaluate_variant_path(&self, path: VariantPath, env: &Env) -> RuntimeResult {
+		let ty = match &*env.evaluate(&path.ty)?.inner() {
+			RuntimeValue::EnumType(ty) => Rc::clone(ty),
+			other => return Err(RuntimeError::NotAnEnum(other.to_owned())),
+		};
+		let variant = ty.variant_index(&path.variant).ok_or_else(|| {
+			RuntimeError::NoSuchMember(RuntimeValue::EnumType(Rc::clone(&ty)), path.variant)
+		})?;
+		let value = if ty.variants[variant].1.is_empty() {
+			RuntimeValue::Variant(VariantValue {
+				ty,
+				variant,
+				payload: Vec::new(),
+			})
+		} else {
+			RuntimeValue::Constructor(ty, variant)
+		};
+		Ok(RuntimeVariable::from(value))
+	}
+
+	fn evaluate_struct_literal(&self, literal: StructLiteral, env: &Env) -> RuntimeResult {
+		let ty = match &*env.evaluate(&literal.name)?.inner() {
+			RuntimeValue::StructType(ty) => Rc::clone(ty),
+			other => return Err(RuntimeError::NotAStruct(other.to_owned())),
+		};
+
+		let mut fields = vec![None; ty.fields.len()];
+		for (ident, expr) in literal.fields {
+			let idx = ty.field_index(&ident).ok_or_else(|| {
+				RuntimeError::NoSuchMember(RuntimeValue::StructType(Rc::clone(&ty)), ident)
+			})?;
+			let value = self.evaluate_in(expr, env)?.inner().to_owned();
+			Self::check_field(&ty, idx, &value)?;
+			fields[idx] = Some(value);
+		}
+		let fields = fields
+			.into_iter()
+			.zip(&ty.fields)
+			.map(|(value, (ident, _))| {
+				value.ok_or_else(|| RuntimeError::MissingField(ty.name.clone(), ident.clone()))
+			})
+			.collect::<Result<_, _>>()?;
+		Ok(RuntimeVariable::from(RuntimeValue::Struct(StructValue {
+			ty,
+			fields,
+		})))
+	}
+
+	/// Nothing is declared unless the whole pattern matches.
 	fn evaluate_declaration(&self, declaration: DeclarationExpression, env: &Env) -> RuntimeResult {
-		let mut value = self.evaluate(*declaration.value)?;
-		value.mutable = declaration.mutable;
-		Ok(env.declare(&declaration.ident, value))
+		let value = self.evaluate_in(*declaration.value, env)?;
+		if let Pattern::Identifier(ident, mutable) = &declaration.pattern {
+			let mut value = value;
+			value.mutable = *mutable;
+			return Ok(env.declare(ident, value));
+		}
+
+		let mut bindings = Vec::new();
+		self.destructure(&declaration.pattern, &value.inner(), env, &mut bindings)?;
+		Self::declare_bindings(env, bindings);
+		Ok(value)
+	}
+
+	fn declare_bindings(env: &Env, bindings: Vec<(&str, bool, RuntimeValue)>) {
+		for (ident, mutable, item) in bindings {
+			let mut item = RuntimeVariable::from(item);
+			item.mutable = mutable;
+			env.declare(ident, item);
+		}
+	}
+
+	fn destructure<'a>(
+		&self,
+		pattern: &'a Pattern,
+		value: &RuntimeValue,
+		env: &Env,
+		bindings: &mut Vec<(&'a str, bool, RuntimeValue)>,
+	) -> Result<(), RuntimeError> {
+		match (pattern, value) {
+			(Pattern::Identifier(ident, mutable), value) => {
+				bindings.push((ident, *mutable, value.to_owned()))
+			}
+			(Pattern::Wildcard, _) => (),
+			(Pattern::Tuple(patterns), RuntimeValue::Tuple(items))
+				if patterns.len() == items.len() =>
+			{
+				for (pattern, item) in patterns.iter().zip(items) {
+					self.destructure(pattern, item, env, bindings)?;
+				}
+			}
+			(Pattern::Tuple(patterns), other) => {
+				return Err(RuntimeError::CannotDestructure(
+					other.to_owned(),
+					patterns.len(),
+				))
+			}
+			(pattern, value) => {
+				if !self.match_pattern(pattern, value, env, bindings)? {
+					return Err(RuntimeError::PatternMismatch(
+						Box::from(pattern.to_string()),
+						value.to_owned(),
+					));
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Each arm binds its names in a scope of its own, which its guard sees too.
+	fn evaluate_match(&self, matching: MatchExpression, env: &Env) -> RuntimeResult {
+		let value = self.evaluate_in(*matching.value, env)?.inner().to_owned();
+		for arm in matching.arms {
+			let mut bindings = Vec::new();
+			if !self.match_pattern(&arm.pattern, &value, env, &mut bindings)? {
+				continue;
+			}
+			let scope = Env::new(env.clone());
+			Self::declare_bindings(&scope, bindings);
+			if let Some(guard) = arm.guard {
+				if !self.evaluate_condition(guard, &scope)? {
+					continue;
+				}
+			}
+			return self.evaluate_in(arm.body, &scope);
+		}
+		Err(RuntimeError::NoMatchingArm(value))
+	}
+
+	fn evaluate_range(&self, range: RangeExpression, env: &Env) -> RuntimeResult {
+		let start = self.evaluate_bound(*range.start, env)?;
+		let end = self.evaluate_bound(*range.end, env)?;
+		let step = match range.step {
+			Some(step) => self.evaluate_bound(*step, env)?,
+			None => 1,
+		};
+		if step == 0 {
+			return Err(RuntimeError::ZeroStep);
+		}
+		Ok(RuntimeVariable::from(RuntimeValue::Range(Box::new(
+			RangeValue {
+				start,
+				end,
+				step,
+				inclusive: range.inclusive,
+			},
+		))))
+	}
+
+	fn evaluate_bound(&self, bound: Expression, env: &Env) -> Result<i128, RuntimeError> {
+		match &*self.evaluate_in(bound, env)?.inner() {
+			RuntimeValue::Number(Numeric::Int(bound)) => Ok(*bound),
+			other => Err(RuntimeError::InvalidRangeBound(other.to_owned())),
+		}
+	}
+
+	/// Every iteration binds the pattern in a scope of its own, so nothing
+	/// declared in the body outlives it.
+	fn evaluate_for(&self, looping: ForExpression, env: &Env) -> RuntimeResult {
+		let iterable = self.evaluate_in(*looping.iterable, env)?;
+		let items = iterable.inner().to_owned().iterate()?;
+		while let Some(item) = items.next(env)? {
+			let mut bindings = Vec::new();
+			self.destructure(&looping.pattern, &item, env, &mut bindings)?;
+			let scope = Env::new(env.clone());
+			Self::declare_bindings(&scope, bindings);
+			match self.evaluate_block(&looping.body, &scope) {
+				Ok(()) | Err(RuntimeError::Continue) => (),
+				Err(RuntimeError::Break) => break,
+				Err(err) => return Err(err),
+			}
+		}
+		Ok(RuntimeVariable::unit())
+	}
+
+	fn evaluate_block(&self, body: &[Expression], env: &Env) -> Result<(), RuntimeError> {
+		for expr in body {
+			self.evaluate_in(expr.to_owned(), env)?;
+		}
+		Ok(())
+	}
+
+	fn evaluate_condition(&self, condition: Expression, env: &Env) -> Result<bool, RuntimeError> {
+		self.evaluate_in(condition, env)?.inner().is_truthy()
+	}
+
+	/// Whether `value` matches `pattern`, collecting the names it binds.
+	/// Values of another type than a literal or range don't match it.
+	fn match_pattern<'a>(
+		&self,
+		pattern: &'a Pattern,
+		value: &RuntimeValue,
+		env: &Env,
+		bindings: &mut Vec<(&'a str, bool, RuntimeValue)>,
+	) -> Result<bool, RuntimeError> {
+		let items = match (pattern, value) {
+			(Pattern::Identifier(ident, mutable), value) => {
+				bindings.push((ident, *mutable, value.to_owned()));
+				return Ok(true);
+			}
+			(Pattern::Wildcard, _) => return Ok(true),
+			(Pattern::Tuple(patterns), RuntimeValue::Tuple(items))
+			| (Pattern::List(patterns), RuntimeValue::List(items))
+				if patterns.len() == items.len() =>
+			{
+				patterns.iter().zip(items)
+			}
+			(Pattern::Tuple(_) | Pattern::List(_), _) => return Ok(false),
+			(Pattern::Literal(literal), value) => {
+				let literal = self.evaluate_in(*literal.to_owned(), env)?;
+				let literal = literal.inner();
+				return Ok(value.same_type(&literal) && *value == *literal);
+			}
+			(Pattern::Range(low, high, inclusive), value) => {
+				let low = self.evaluate_in(*low.to_owned(), env)?.inner().to_owned();
+				let high = self.evaluate_in(*high.to_owned(), env)?.inner().to_owned();
+				if !value.same_type(&low) || !value.same_type(&high) {
+					return Ok(false);
+				}
+				let above = value
+					.compare(&low, BinaryOp::GreaterEqual)?
+					.is_some_and(Ordering::is_ge);
+				let below = value
+					.compare(&high, BinaryOp::LessEqual)?
+					.is_some_and(|ordering| match inclusive {
+						true => ordering.is_le(),
+						false => ordering.is_lt(),
+					});
+				return Ok(above && below);
+			}
+			(Pattern::Variant(path, patterns), value) => {
+				let ty = match &*env.evaluate(&path.ty)?.inner() {
+					RuntimeValue::EnumType(ty) => Rc::clone(ty),
+					other => return Err(RuntimeError::NotAnEnum(other.to_owned())),
+				};
+				let variant = ty.variant_index(&path.variant).ok_or_else(|| {
+					RuntimeError::NoSuchMember(
+						RuntimeValue::EnumType(Rc::clone(&ty)),
+						path.variant.clone(),
+					)
+				})?;
+				let arity = ty.variants[variant].1.len();
+				if patterns.len() != arity {
+					return Err(RuntimeError::PayloadCountMismatch(
+						Box::from(ty.variant_name(variant)),
+						arity,
+						patterns.len(),
+					));
+				}
+				match value {
+					RuntimeValue::Variant(value)
+						if Rc::ptr_eq(&value.ty, &ty) && value.variant == variant =>
+					{
+						patterns.iter().zip(&value.payload)
+					}
+					_ => return Ok(false),
+				}
+			}
+		};
+		for (pattern, item) in items {
+			if !self.match_pattern(pattern, item, env, bindings)? {
+				return Ok(false);
+			}
+		}
+		Ok(true)
+	}
+
+	fn evaluate_call(&self, call: CallExpression, env: &Env) -> RuntimeResult {
+		let mut args = Vec::with_capacity(call.args.len() + 1);
+		let callee = match *call.callee {
+			// `value.method(args)` passes the value as the first argument
+			Expression::Member(member) => {
+				let object = self.evaluate_in(*member.object, env)?.inner().to_owned();
+				match builtins::method(&object, &member.ident) {
+					Some(method) => {
+						args.push(object);
+						RuntimeValue::Function(method)
+					}
+					None => Self::member_of(&object, member.ident)?,
+				}
+			}
+			callee => self.evaluate_in(callee, env)?.inner().to_owned(),
+		};
+		for arg in call.args {
+			args.push(self.evaluate_in(arg, env)?.inner().to_owned());
+		}
+
+		callee.call(env, args).map(RuntimeVariable::from)
+	}
+
+	fn evaluate_member(&self, member: MemberExpression, env: &Env) -> RuntimeResult {
+		let object = self.evaluate_in(*member.object, env)?;
+		let value = Self::member_of(&object.inner(), member.ident)?;
+		Ok(RuntimeVariable::from(value))
+	}
+
+	fn member_of(object: &RuntimeValue, ident: Box<str>) -> Result<RuntimeValue, RuntimeError> {
+		match object {
+			RuntimeValue::Namespace(namespace) => namespace
+				.get(&ident)
+				.ok_or_else(|| RuntimeError::NoSuchMember(object.to_owned(), ident)),
+			RuntimeValue::Struct(structure) => structure
+				.field(&ident)
+				.cloned()
+				.ok_or_else(|| RuntimeError::NoSuchMember(object.to_owned(), ident)),
+			RuntimeValue::Variant(variant) => variant
+				.field(&ident)
+				.cloned()
+				.ok_or_else(|| RuntimeError::NoSuchMember(object.to_owned(), ident)),
+			other => Err(RuntimeError::NoSuchMember(other.to_owned(), ident)),
+		}
+	}
+
+	fn evaluate_index(&self, index: IndexExpression, env: &Env) -> RuntimeResult {
+		let object = self.evaluate_in(*index.object, env)?;
+		let idx = self.evaluate_in(*index.index, env)?;
+		let item = object.inner().index(&idx.inner())?;
+		Ok(RuntimeVariable::from(item))
+	}
+
+	fn evaluate_items(
+		&self,
+		items: Vec<Expression>,
+		env: &Env,
+	) -> Result<Vec<RuntimeValue>, RuntimeError> {
+		items
+			.into_iter()
+			.map(|item| {
+				self.evaluate_in(item, env)
+					.map(|item| item.inner().to_owned())
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{error::RuntimeError, Runtime};
+	use crate::{environment::Env, numeric::error::NumericError, parser::Parser};
+
+	fn run(source: &str) -> Result<String, RuntimeError> {
+		let ast = Parser::new().produce_ast(source, false).unwrap();
+		let runtime = Runtime::new(Env::global());
+		runtime
+			.evaluate(ast)
+			.map(|result| result.inner().to_string())
+	}
+
+	fn eval(source: &str) -> String {
+		run(source).unwrap()
+	}
+
+	const SHAPE: &str = "enum Shape { Circle(r), Square(s), Empty };";
+	const POINT: &str = "struct Point { x: int, y: int };";
+
+	#[test]
+	fn the_first_matching_arm_wins() {
+		let classify = |n: i32| {
+			eval(&format!(
+				r#"match {n} {{ 0 => "zero", 1..3 => "low", 3..=5 => "mid", _ => "high" }}"#
+			))
+		};
+		assert_eq!(classify(0), r#""zero""#);
+		assert_eq!(classify(2), r#""low""#);
+		assert_eq!(classify(3), r#""mid""#);
+		assert_eq!(classify(5), r#""mid""#);
+		assert_eq!(classify(6), r#""high""#);
+		assert_eq!(eval(r#"match "b" { "a" => 1, "b" => 2, _ => 3 }"#), "2");
+	}
+
+	#[test]
+	fn guards_fall_through_to_later_arms() {
+		let source = "match (1, 2) { (x, y) if x > y => x, (_, y) => y }";
+		assert_eq!(eval(source), "2");
+		assert!(matches!(
+			run("match 1 { x if x => 1, _ => 2 }"),
+			Ok(value) if value == "1"
+		));
+		assert!(matches!(
+			run(r#"match 1 { x if "yes" => 1, _ => 2 }"#),
+			Err(RuntimeError::NotACondition(_))
+		));
+	}
+
+	#[test]
+	fn variant_patterns_bind_payloads() {
+		let area = |shape: &str| {
+			eval(&format!(
+				"{SHAPE} match {shape} {{ \
+				Shape::Circle(r) => 3 * r * r, Shape::Square(s) => s * s, Shape::Empty => 0 }}"
+			))
+		};
+		assert_eq!(area("Shape::Circle(2)"), "12");
+		assert_eq!(area("Shape::Square(3)"), "9");
+		assert_eq!(area("Shape::Empty"), "0");
+		assert!(matches!(
+			run(&format!(
+				"{SHAPE} match Shape::Circle(1) {{ Shape::Circle(a, b) => 1, _ => 2 }}"
+			)),
+			Err(RuntimeError::PayloadCountMismatch(_, 1, 2))
+		));
+	}
+
+	#[test]
+	fn nested_and_list_patterns() {
+		assert_eq!(
+			eval("match (1, (2, 3)) { (a, (2, c)) => a + c, _ => 0 }"),
+			"4"
+		);
+		assert_eq!(
+			eval("match [1, 2] { [a] => a, [a, b] => a + b, _ => 0 }"),
+			"3"
+		);
+		assert_eq!(eval("match -1 { -1 => 1, _ => 0 }"), "1");
+	}
+
+	#[test]
+	fn unmatched_values_fail() {
+		assert!(matches!(
+			run("match 7 { 1 => 1 }"),
+			Err(RuntimeError::NoMatchingArm(_))
+		));
+		assert!(matches!(
+			run("let (a, 1) = (5, 2)"),
+			Err(RuntimeError::PatternMismatch(..))
+		));
+		assert_eq!(eval("let (a, 1) = (5, 1); a"), "5");
+	}
+
+	#[test]
+	fn arm_bindings_stay_in_their_arm() {
+		assert!(matches!(
+			run("match 1 { x => x }; x"),
+			Err(RuntimeError::VariableNotDeclared(_))
+		));
+		assert_eq!(eval("let x = 5; match 1 { x => x }; x"), "5");
+	}
+
+	#[test]
+	fn for_loops_go_through_ranges() {
+		assert_eq!(eval("let ~s = 0; for i in 0..5 { s = s + i }; s"), "10");
+		assert_eq!(eval("let ~s = 0; for i in 0..=5 { s = s + i }; s"), "15");
+		assert_eq!(
+			eval("let ~s = 0; for i in 10..0 step -3 { s = s + i }; s"),
+			"22"
+		);
+		assert_eq!(eval("let ~n = 0; for i in 5..5 { n = n + 1 }; n"), "0");
+		assert_eq!(eval("for i in 0..3 { i }"), "_");
+	}
+
+	#[test]
+	fn break_and_continue() {
+		let source = "let ~s = 0; \
+			for i in 0..=10 { match i { 3 => continue, 6 => break, _ => s = s + i } }; s";
+		assert_eq!(eval(source), "12");
+		// break only leaves the innermost loop
+		let source = "let ~s = 0; \
+			for i in 0..3 { for j in 0..3 { match j { 1 => break, _ => s = s + 1 } } }; s";
+		assert_eq!(eval(source), "3");
+	}
+
+	#[test]
+	fn for_loops_destructure_items() {
+		let source = "let ~s = 0; for (a, b) in [(1, 2), (3, 4)] { s = s + a * b }; s";
+		assert_eq!(eval(source), "14");
+		assert!(matches!(
+			run("for (a, 1) in [(1, 2)] { a }"),
+			Err(RuntimeError::PatternMismatch(..))
+		));
+		assert_eq!(
+			eval(r#"let ~s = ""; for ch in "héllo" { s = s + ch }; s"#),
+			r#""héllo""#
+		);
+	}
+
+	#[test]
+	fn invalid_ranges() {
+		assert!(matches!(run("0..5 step 0"), Err(RuntimeError::ZeroStep)));
+		assert!(matches!(
+			run("1.5..3"),
+			Err(RuntimeError::InvalidRangeBound(_))
+		));
+		assert!(matches!(
+			run("for i in 5 { i }"),
+			Err(RuntimeError::NotIterable(_))
+		));
+	}
+
+	#[test]
+	fn iterator_adapters_are_lazy() {
+		let huge = "(0..170141183460469231731687303715884105727)";
+		assert_eq!(
+			eval(&format!("{huge}.map(math.abs).skip(1).take(3).collect()")),
+			"[1, 2, 3]"
+		);
+		// the string is never passed to math.abs
+		assert_eq!(eval(r#"[-1, "a"].map(math.abs).take(1).collect()"#), "[1]");
+		assert!(run(r#"[-1, "a"].map(math.abs).collect()"#).is_err());
+	}
+
+	#[test]
+	fn iterator_consumers() {
+		assert_eq!(eval("(0..10).filter(math.abs).count()"), "9");
+		assert_eq!(eval("(0..3).chain(7..9).sum()"), "18");
+		assert_eq!(eval("[1.5, 2].sum()"), "3.5");
+		assert_eq!(eval("[].sum()"), "0");
+		assert_eq!(eval("(1..5).fold(0, math.max)"), "4");
+		assert_eq!(eval("[0, 0, 2].any(math.abs)"), "1");
+		assert_eq!(eval("[1, 0, 2].all(math.abs)"), "0");
+		assert_eq!(
+			eval(r#""ab".enumerate().collect()"#),
+			"[(0, 'a'), (1, 'b')]"
+		);
+	}
+
+	#[test]
+	fn next_advances_one_item() {
+		let source = "let it = (0..5).iter(); it.next(); iterator.next(it); it.collect()";
+		assert_eq!(eval(source), "[2, 3, 4]");
+		assert_eq!(eval("iterator.next((0..0).iter())"), "_");
+		assert_eq!(
+			eval("let it = (0..3).iter(); for i in it { break }; it.next()"),
+			"1"
+		);
+	}
+
+	#[test]
+	fn invalid_iterator_arguments() {
+		assert!(matches!(
+			run("(0..3).map(5)"),
+			Err(RuntimeError::InvalidArgument(..))
+		));
+		assert!(matches!(
+			run("(0..3).take(-1)"),
+			Err(RuntimeError::InvalidArgument(..))
+		));
+		assert!(matches!(
+			run("(0..3).flat_map(math.abs).collect()"),
+			Err(RuntimeError::NotIterable(_))
+		));
+	}
+
+	#[test]
+	fn huge_repetitions_fail() {
+		assert_eq!(eval(r#""ab".repeat(3)"#), r#""ababab""#);
+		assert_eq!(eval(r#""ab" * 2"#), r#""abab""#);
+		assert!(matches!(
+			run(r#""ab".repeat(2 ** 62)"#),
+			Err(RuntimeError::InvalidArgument(..))
+		));
+		assert!(matches!(
+			run(r#""ab" * 2 ** 62"#),
+			Err(RuntimeError::InvalidArgument(..))
+		));
+	}
+
+	#[test]
+	fn interpolation_embeds_any_expression() {
+		let source = r#"let x = 2; "{1 + 2} {-x} {[1, x]} {1/2r} {{x}}""#;
+		assert_eq!(eval(source), r#""3 -2 [1, 2] 1/2 {x}""#);
+		assert_eq!(eval(r#""{"a" + "b"}!""#), r#""ab!""#);
+	}
+
+	#[test]
+	fn plain_strings_work_as_format_templates() {
+		let source = r#"let name = "pi"; format("{:>8.2} | {} | {0:?}", 3.14159, name)"#;
+		assert_eq!(eval(source), r#""    3.14 | pi | 3.14159""#);
+		assert_eq!(
+			eval(r#"let x = 3.14159; format("{x:>8.2}")"#),
+			r#""    3.14""#
+		);
+		assert_eq!(eval(r#"let x = 1; format("{{x}} {x}")"#), r#""1 1""#);
+		assert_eq!(eval(r#"format(r"{{}} {:05}", 42)"#), r#""{} 00042""#);
+	}
+
+	#[test]
+	fn bitwise_operators_bind_looser_than_comparisons() {
+		assert_eq!(eval("4 | 1 == 1"), "5");
+		assert_eq!(eval("let a = 6; let b = 1; a & b == 1"), "0");
+		assert_eq!(eval("1 < 2 ^ 3"), "2");
+		assert_eq!(eval("1 | 2 ^ 3 & 5"), "3");
+		assert_eq!(eval("1 << 2 < 5"), "1");
+		assert_eq!(eval("2 + 3 & 6"), "4");
+	}
+
+	#[test]
+	fn mismatched_units_are_a_runtime_error() {
+		assert_eq!(eval("5 km + 3 m"), "5003 m");
+		assert!(matches!(
+			run("5 m + 2 s"),
+			Err(RuntimeError::DimensionMismatch(..))
+		));
+		assert!(matches!(
+			run("5 m < 2 s"),
+			Err(RuntimeError::DimensionMismatch(..))
+		));
+		assert!(matches!(
+			run("(1 m) ** (2 ** 30) * (1 m) ** (2 ** 30)"),
+			Err(RuntimeError::Arithmetic(NumericError::DimensionOverflow(
+				..
+			)))
+		));
+	}
+
+	#[test]
+	fn non_finite_floats_convert_to_plain_text() {
+		assert_eq!(eval("math.inf"), "math.inf");
+		assert_eq!(eval(r#""{math.inf} {math.nan}""#), r#""inf nan""#);
+		assert_eq!(
+			eval(r#"format("{}|{:.2}", -math.inf, math.nan)"#),
+			r#""-inf|nan""#
+		);
+		assert_eq!(eval(r#"format_number(math.nan, "")"#), r#""nan""#);
+		assert_eq!(eval(r#"format_number(math.inf, ">+6.1e")"#), r#""  +inf""#);
+	}
+
+	#[test]
+	fn equality_goes_by_value_across_number_types() {
+		assert_eq!(eval("1 == 1.0"), "1");
+		assert_eq!(eval("1/2r == 0.5"), "1");
+		assert_eq!(eval("0.5d == 1/2r"), "1");
+		assert_eq!(eval("2 ** 70 == 2.0 ** 70"), "1");
+		// floats compare exactly, not after rounding the other operand
+		assert_eq!(eval("0.1 == 0.1d"), "0");
+		assert_eq!(eval("1 != 1.5"), "1");
+	}
+
+	#[test]
+	fn nan_is_unordered() {
+		assert_eq!(eval("math.nan == math.nan"), "0");
+		assert_eq!(eval("math.nan != math.nan"), "1");
+		assert_eq!(eval("math.nan < 1"), "0");
+		assert_eq!(eval("math.nan >= 1"), "0");
+	}
+
+	#[test]
+	fn relational_operators_across_number_types() {
+		assert_eq!(eval("2 ** 100 > 2 ** 64"), "1");
+		assert_eq!(eval("2 ** 100 > 1"), "1");
+		assert_eq!(eval("1/3r < 0.34d"), "1");
+		assert_eq!(eval("0.3d <= 3/10r"), "1");
+		assert_eq!(eval("-(2 ** 100) < 1/2r"), "1");
+		assert_eq!(eval("3 >= 3.5"), "0");
+		assert!(matches!(
+			run(r#"1 < "a""#),
+			Err(RuntimeError::UnsupportedOperation(_))
+		));
+	}
+
+	#[test]
+	fn equality_ignores_mutability() {
+		assert_eq!(eval("let ~x = 1; x == 1"), "1");
+		assert_eq!(eval("let ~x = 2; let y = 2; x != y"), "0");
+	}
+
+	#[test]
+	fn strings_compare_by_code_point() {
+		assert_eq!(eval(r#""a" < "b""#), "1");
+		assert_eq!(eval(r#""file10" < "file9""#), "1");
+		assert_eq!(eval(r#""B" >= "a""#), "0");
+		assert_eq!(eval(r#"compare("file10", "file9", "natural")"#), "1");
+		assert_eq!(eval(r#"compare("B", "a", "case_insensitive")"#), "1");
+		assert_eq!(eval(r#"compare("B", "a", "code_point")"#), "-1");
+	}
+
+	#[test]
+	fn unknown_compare_modes_list_the_valid_ones() {
+		let error = run(r#"compare("a", "b", "lexical")"#).unwrap_err();
+		assert!(matches!(error, RuntimeError::UnknownMode(..)));
+		assert_eq!(
+			error.to_string(),
+			r#"Unknown mode "lexical" for function 'compare', expected one of "code_point", "case_insensitive", "natural""#
+		);
+	}
+
+	#[test]
+	fn tuples_destructure_into_bindings() {
+		assert_eq!(eval("let (a, b) = (1, 2); a + b"), "3");
+		assert_eq!(eval("let (p, (q, r)) = (1, (2, 3)); p + q * r"), "7");
+		assert_eq!(eval("let (~a, b) = (1, 2); a = a + b; a"), "3");
+		assert!(matches!(
+			run("let (a, b) = (1, 2); a = 5"),
+			Err(RuntimeError::CannotMutateVariable(_))
+		));
+	}
+
+	#[test]
+	fn destructuring_checks_the_arity() {
+		let error = run("let (x, y) = (1, 2, 3)").unwrap_err();
+		assert!(matches!(error, RuntimeError::CannotDestructure(_, 2)));
+		assert_eq!(
+			error.to_string(),
+			"Cannot destructure (1, 2, 3) into a tuple of 2 items"
+		);
+		assert!(matches!(
+			run("let (x, y) = 5"),
+			Err(RuntimeError::CannotDestructure(_, 2))
+		));
+		assert!(matches!(
+			run("let (x, (y, z)) = (1, (2,))"),
+			Err(RuntimeError::CannotDestructure(_, 2))
+		));
+	}
+
+	#[test]
+	fn struct_declarations_and_literals() {
+		let point = |source: &str| eval(&format!("{POINT} {source}"));
+		assert_eq!(point("Point"), "struct Point { x: int, y: int }");
+		assert_eq!(point("Point { y: 2, x: 1 }"), "Point { x: 1, y: 2 }");
+		assert_eq!(point("let p = Point { x: 1, y: 2 }; p.x + p.y"), "3");
+		assert!(matches!(
+			run("struct Bad { x: nope }"),
+			Err(RuntimeError::UnknownType(_))
+		));
+	}
+
+	#[test]
+	fn struct_literals_check_their_fields() {
+		let point = |source: &str| run(&format!("{POINT} {source}"));
+		assert!(matches!(
+			point("Point { x: 1 }"),
+			Err(RuntimeError::MissingField(ty, field)) if &*ty == "Point" && &*field == "y"
+		));
+		assert!(matches!(
+			point("Point { x: 1, y: 2, z: 3 }"),
+			Err(RuntimeError::NoSuchMember(_, field)) if &*field == "z"
+		));
+		assert!(matches!(
+			point("Point { x: 1.5, y: 2 }"),
+			Err(RuntimeError::FieldTypeMismatch(..))
+		));
+		assert!(matches!(
+			point("let p = Point { x: 1, y: 2 }; p.z"),
+			Err(RuntimeError::NoSuchMember(..))
+		));
+	}
+
+	#[test]
+	fn fields_update_only_on_mutable_bindings() {
+		let point = |source: &str| run(&format!("{POINT} {source}"));
+		assert_eq!(
+			point("let ~p = Point { x: 1, y: 2 }; p.x = 5; p").unwrap(),
+			"Point { x: 5, y: 2 }"
+		);
+		assert!(matches!(
+			point("let p = Point { x: 1, y: 2 }; p.x = 5"),
+			Err(RuntimeError::CannotMutateVariable(_))
+		));
+		assert!(matches!(
+			point(r#"let ~p = Point { x: 1, y: 2 }; p.x = "a""#),
+			Err(RuntimeError::FieldTypeMismatch(..))
+		));
+	}
+
+	#[test]
+	fn each_struct_declaration_is_a_distinct_type() {
+		let source = format!("{POINT} struct Other {{ x: int, y: int }};");
+		let two = |source2: &str| run(&format!("{source} {source2}"));
+		assert_eq!(
+			two("Point { x: 1, y: 2 } == Point { x: 1, y: 2 }").unwrap(),
+			"1"
+		);
+		assert!(matches!(
+			two("Point { x: 1, y: 2 } == Other { x: 1, y: 2 }"),
+			Err(RuntimeError::UnsupportedOperation(_))
+		));
+		assert!(matches!(
+			two("struct Line { a: Point }; Line { a: Other { x: 1, y: 2 } }"),
+			Err(RuntimeError::FieldTypeMismatch(..))
+		));
+	}
+
+	#[test]
+	fn variants_carry_payloads() {
+		let shape = |source: &str| eval(&format!("{SHAPE} {source}"));
+		assert_eq!(shape("Shape::Circle(2)"), "Shape::Circle(2)");
+		assert_eq!(shape("Shape::Empty"), "Shape::Empty");
+		assert_eq!(shape("Shape::Circle(1) == Shape::Circle(1)"), "1");
+		assert_eq!(shape("Shape::Circle(1) == Shape::Circle(2)"), "0");
+		assert_eq!(shape("Shape::Circle(1) == Shape::Square(1)"), "0");
+		let pair = "enum Pair { Two(a, b) }; match Pair::Two(3, 4) { Pair::Two(a, b) => a * b }";
+		assert_eq!(eval(pair), "12");
+	}
+
+	#[test]
+	fn variants_of_different_enums_dont_compare() {
+		let source = format!("{SHAPE} enum Tile {{ Empty, Wall }}; Shape::Empty == Tile::Empty");
+		assert!(matches!(
+			run(&source),
+			Err(RuntimeError::UnsupportedOperation(_))
+		));
+	}
+
+	#[test]
+	fn constructors_check_the_payload_count() {
+		let shape = |source: &str| run(&format!("{SHAPE} {source}"));
+		assert!(matches!(
+			shape("Shape::Circle(1, 2)"),
+			Err(RuntimeError::WrongArgumentCount(name, 1, 2)) if &*name == "Shape::Circle"
+		));
+		assert!(matches!(
+			shape("Shape::Circle()"),
+			Err(RuntimeError::WrongArgumentCount(_, 1, 0))
+		));
+		assert!(matches!(
+			shape("Shape::Empty(1)"),
+			Err(RuntimeError::NotCallable(_))
+		));
+		assert!(matches!(
+			shape("Shape::Triangle(1)"),
+			Err(RuntimeError::NoSuchMember(..))
+		));
 	}
 }
@@ -0,0 +1,91 @@
+use std::fmt::Display;
+
+/// An integer range, whose items are only computed while iterating.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeValue {
+	pub start: i128,
+	pub end: i128,
+	/// Never zero, and negative for ranges counting down.
+	pub step: i128,
+	pub inclusive: bool,
+}
+
+impl RangeValue {
+	/// Whether `item` is one of the items past the start.
+	fn before_end(&self, item: i128) -> bool {
+		match (self.step > 0, self.inclusive) {
+			(true, false) => item < self.end,
+			(true, true) => item <= self.end,
+			(false, false) => item > self.end,
+			(false, true) => item >= self.end,
+		}
+	}
+
+	pub fn iter(self) -> impl Iterator<Item = i128> {
+		std::iter::successors(Some(self.start), move |item| item.checked_add(self.step))
+			.take_while(move |&item| self.before_end(item))
+	}
+
+	/// The item at `idx`, without going through the ones before it.
+	pub fn get(&self, idx: usize) -> Option<i128> {
+		let offset = i128::try_from(idx).ok()?.checked_mul(self.step)?;
+		let item = self.start.checked_add(offset)?;
+		self.before_end(item).then_some(item)
+	}
+}
+
+impl Display for RangeValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let dots = if self.inclusive { "..=" } else { ".." };
+		write!(f, "{}{dots}{}", self.start, self.end)?;
+		if self.step != 1 {
+			write!(f, " step {}", self.step)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::RangeValue;
+
+	fn range(start: i128, end: i128, step: i128, inclusive: bool) -> RangeValue {
+		RangeValue {
+			start,
+			end,
+			step,
+			inclusive,
+		}
+	}
+
+	#[test]
+	fn items() {
+		assert!(range(0, 5, 1, false).iter().eq(0..5));
+		assert!(range(0, 5, 1, true).iter().eq(0..=5));
+		assert!(range(0, 10, 3, false).iter().eq([0, 3, 6, 9]));
+		assert!(range(10, 0, -3, true).iter().eq([10, 7, 4, 1]));
+		assert_eq!(range(5, 0, 1, false).iter().count(), 0);
+	}
+
+	#[test]
+	fn items_near_the_end_of_i128_dont_overflow() {
+		let top = range(i128::MAX - 2, i128::MAX, 1, true);
+		assert!(top.iter().eq([i128::MAX - 2, i128::MAX - 1, i128::MAX]));
+		assert_eq!(range(i128::MIN + 1, i128::MIN, -2, true).iter().count(), 1);
+	}
+
+	#[test]
+	fn get_skips_to_the_item() {
+		let range = range(10, 0, -3, true);
+		assert_eq!(range.get(0), Some(10));
+		assert_eq!(range.get(3), Some(1));
+		assert_eq!(range.get(4), None);
+		assert_eq!(range.get(usize::MAX), None);
+	}
+
+	#[test]
+	fn display() {
+		assert_eq!(range(0, 5, 1, false).to_string(), "0..5");
+		assert_eq!(range(0, 5, 2, true).to_string(), "0..=5 step 2");
+	}
+}
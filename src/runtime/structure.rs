@@ -0,0 +1,132 @@
+use std::{fmt::Display, rc::Rc};
+
+use crate::{expression::structure::write_fields, numeric::Numeric};
+
+use super::{enumeration::EnumType, value::RuntimeValue};
+
+/// The type of a struct field, either built in or a struct or enum.
+#[derive(Debug)]
+pub enum FieldType {
+	Any,
+	Int,
+	Float,
+	Number,
+	String,
+	Char,
+	Bytes,
+	List,
+	Tuple,
+	Struct(Rc<StructType>),
+	Enum(Rc<EnumType>),
+}
+
+impl FieldType {
+	/// `None` for names that aren't built in, which may name a struct or enum.
+	pub fn builtin(name: &str) -> Option<Self> {
+		Some(match name {
+			"any" => FieldType::Any,
+			"int" => FieldType::Int,
+			"float" => FieldType::Float,
+			"number" => FieldType::Number,
+			"string" => FieldType::String,
+			"char" => FieldType::Char,
+			"bytes" => FieldType::Bytes,
+			"list" => FieldType::List,
+			"tuple" => FieldType::Tuple,
+			_ => return None,
+		})
+	}
+
+	pub fn matches(&self, value: &RuntimeValue) -> bool {
+		match (self, value) {
+			(FieldType::Any, _) => true,
+			(FieldType::Int, RuntimeValue::Number(number)) => {
+				matches!(number, Numeric::Int(_) | Numeric::Big(_))
+			}
+			(FieldType::Float, RuntimeValue::Number(number)) => {
+				matches!(number, Numeric::Float(_))
+			}
+			(FieldType::Number, RuntimeValue::Number(_)) => true,
+			(FieldType::String, RuntimeValue::String(_)) => true,
+			(FieldType::Char, RuntimeValue::Char(_)) => true,
+			(FieldType::Bytes, RuntimeValue::Bytes(_)) => true,
+			(FieldType::List, RuntimeValue::List(_)) => true,
+			(FieldType::Tuple, RuntimeValue::Tuple(_)) => true,
+			(FieldType::Struct(ty), RuntimeValue::Struct(value)) => Rc::ptr_eq(ty, &value.ty),
+			(FieldType::Enum(ty), RuntimeValue::Variant(value)) => Rc::ptr_eq(ty, &value.ty),
+			_ => false,
+		}
+	}
+}
+
+impl Display for FieldType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			FieldType::Any => "any",
+			FieldType::Int => "int",
+			FieldType::Float => "float",
+			FieldType::Number => "number",
+			FieldType::String => "string",
+			FieldType::Char => "char",
+			FieldType::Bytes => "bytes",
+			FieldType::List => "list",
+			FieldType::Tuple => "tuple",
+			FieldType::Struct(ty) => &ty.name,
+			FieldType::Enum(ty) => &ty.name,
+		})
+	}
+}
+
+/// A declared struct. Every declaration makes a distinct type, even if one
+/// with the same name and fields exists.
+#[derive(Debug)]
+pub struct StructType {
+	pub name: Box<str>,
+	pub fields: Vec<(Box<str>, FieldType)>,
+}
+
+impl PartialEq for StructType {
+	fn eq(&self, other: &Self) -> bool {
+		std::ptr::eq(self, other)
+	}
+}
+
+impl StructType {
+	pub fn field_index(&self, ident: &str) -> Option<usize> {
+		self.fields.iter().position(|(field, _)| **field == *ident)
+	}
+}
+
+impl Display for StructType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "struct {} {{", self.name)?;
+		write_fields(f, &self.fields)
+	}
+}
+
+/// A struct with its field values, in the order of its type's fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructValue {
+	pub ty: Rc<StructType>,
+	pub fields: Vec<RuntimeValue>,
+}
+
+impl StructValue {
+	pub fn field(&self, ident: &str) -> Option<&RuntimeValue> {
+		self.ty.field_index(ident).map(|idx| &self.fields[idx])
+	}
+}
+
+impl Display for StructValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} {{", self.ty.name)?;
+		let fields = self
+			.ty
+			.fields
+			.iter()
+			.map(|(ident, _)| ident)
+			.zip(&self.fields)
+			.collect::<Box<[_]>>();
+		write_fields(f, &fields)
+	}
+}
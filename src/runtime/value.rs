@@ -1,16 +1,30 @@
 use std::{
+	cmp::Ordering,
 	fmt::{Debug, Display},
 	mem::Discriminant,
-	ops::{Add, Div, Mul, Neg, Rem, Sub},
+	ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Shl, Shr, Sub},
+	rc::Rc,
 };
 
+use num_traits::Pow;
+
 use crate::{
+	builtins::{Builtin, Namespace},
+	environment::Env,
 	expression::{binary::BinaryOp, unary::UnaryOp},
+	helpers::{checked_repeat, tuple_to_string},
 	numeric::Numeric,
+	regex::Regex,
 	runtime::error::{RuntimeError, RuntimeOperation},
 };
 
-use super::variable::Pos;
+use super::{
+	enumeration::{EnumType, VariantValue},
+	iterator::IteratorValue,
+	range::RangeValue,
+	structure::{StructType, StructValue},
+	variable::{FloorDiv, Modulo, Pos},
+};
 
 type InnerRuntimeResult = Result<RuntimeValue, RuntimeError>;
 
@@ -19,6 +33,21 @@ pub enum RuntimeValue {
 	Unit,
 	Number(Numeric),
 	String(String),
+	Char(char),
+	Bytes(Vec<u8>),
+	Function(Builtin),
+	Namespace(&'static Namespace),
+	List(Vec<RuntimeValue>),
+	Tuple(Vec<RuntimeValue>),
+	Range(Box<RangeValue>),
+	Iterator(IteratorValue),
+	Regex(Rc<Regex>),
+	StructType(Rc<StructType>),
+	Struct(StructValue),
+	EnumType(Rc<EnumType>),
+	/// Builds the variant with this index from its payload.
+	Constructor(Rc<EnumType>, usize),
+	Variant(VariantValue),
 }
 
 impl Display for RuntimeValue {
@@ -29,6 +58,36 @@ impl Display for RuntimeValue {
 			Unit => f.write_str("_"),
 			Number(number) => Display::fmt(number, f),
 			String(st) => Debug::fmt(st, f),
+			Char(ch) => Debug::fmt(ch, f),
+			Bytes(bytes) => write!(
+				f,
+				"bytes[{}]",
+				bytes
+					.iter()
+					.map(|byte| format!("{byte:02x}"))
+					.collect::<Box<[_]>>()
+					.join(" ")
+			),
+			Function(builtin) => write!(f, "fn {}", builtin.name),
+			Namespace(namespace) => write!(f, "namespace {}", namespace.name),
+			List(items) => write!(
+				f,
+				"[{}]",
+				items
+					.iter()
+					.map(|item| item.to_string())
+					.collect::<Box<[_]>>()
+					.join(", ")
+			),
+			Tuple(items) => f.write_str(&tuple_to_string(items)),
+			Range(range) => Display::fmt(range, f),
+			Iterator(iterator) => Display::fmt(iterator, f),
+			Regex(regex) => write!(f, "regex.compile({:?})", regex.pattern()),
+			StructType(ty) => Display::fmt(ty, f),
+			Struct(value) => Display::fmt(value, f),
+			EnumType(ty) => Display::fmt(ty, f),
+			Constructor(ty, variant) => write!(f, "fn {}", ty.variant_name(*variant)),
+			Variant(value) => Display::fmt(value, f),
 		}
 	}
 }
@@ -37,6 +96,135 @@ impl RuntimeValue {
 	pub fn discriminant(&self) -> Discriminant<Self> {
 		std::mem::discriminant(self)
 	}
+
+	/// Structs and enums are only of the same type if they come from the same
+	/// declaration. All variants of an enum are of the same type.
+	pub fn same_type(&self, other: &Self) -> bool {
+		match (self, other) {
+			(RuntimeValue::Struct(l), RuntimeValue::Struct(r)) => Rc::ptr_eq(&l.ty, &r.ty),
+			(RuntimeValue::Variant(l), RuntimeValue::Variant(r)) => Rc::ptr_eq(&l.ty, &r.ty),
+			(l, r) => l.discriminant() == r.discriminant(),
+		}
+	}
+
+	/// The value as plain text, as it is embedded in interpolated strings.
+	/// Unlike `Display`, strings are not quoted and numbers use
+	/// `Numeric::to_text`.
+	pub fn to_text(&self) -> String {
+		match self {
+			RuntimeValue::String(st) => st.to_owned(),
+			RuntimeValue::Char(ch) => ch.to_string(),
+			RuntimeValue::Number(number) => number.to_text(),
+			other => other.to_string(),
+		}
+	}
+
+	/// Strings are indexed by char, not by byte.
+	pub fn index(&self, index: &RuntimeValue) -> Result<RuntimeValue, RuntimeError> {
+		use RuntimeValue::*;
+
+		let position = match index {
+			Number(Numeric::Int(idx)) => usize::try_from(*idx).ok(),
+			_ => None,
+		};
+		let item = match (self, position) {
+			(String(st), Some(idx)) => st.chars().nth(idx).map(Char),
+			(List(items) | Tuple(items), Some(idx)) => items.get(idx).cloned(),
+			(Bytes(bytes), Some(idx)) => bytes
+				.get(idx)
+				.map(|byte| Number(Numeric::Int(i128::from(*byte)))),
+			(Range(range), Some(idx)) => range.get(idx).map(|item| Number(Numeric::Int(item))),
+			(String(_) | List(_) | Tuple(_) | Bytes(_) | Range(_), None) => None,
+			(other, _) => return Err(RuntimeError::NotIndexable(other.to_owned())),
+		};
+		item.ok_or_else(|| RuntimeError::InvalidIndex(self.to_owned(), index.to_owned()))
+	}
+
+	/// An iterator over the numbers of a range, the chars of a string, the
+	/// items of a list or tuple, or the bytes of a bytes value. An iterator is
+	/// returned as is, so it keeps its position.
+	pub fn iterate(self) -> Result<IteratorValue, RuntimeError> {
+		use RuntimeValue::*;
+
+		Ok(match self {
+			Iterator(iterator) => iterator,
+			Range(range) => {
+				IteratorValue::items(range.iter().map(|item| Number(Numeric::Int(item))))
+			}
+			String(st) => {
+				let mut offset = 0;
+				IteratorValue::items(std::iter::from_fn(move || {
+					let ch = st[offset..].chars().next()?;
+					offset += ch.len_utf8();
+					Some(Char(ch))
+				}))
+			}
+			List(items) | Tuple(items) => IteratorValue::items(items.into_iter()),
+			Bytes(bytes) => IteratorValue::items(
+				bytes
+					.into_iter()
+					.map(|byte| Number(Numeric::Int(i128::from(byte)))),
+			),
+			other => return Err(RuntimeError::NotIterable(other)),
+		})
+	}
+
+	/// Calls a builtin function or builds an enum variant from `args`.
+	pub fn call(&self, env: &Env, args: Vec<RuntimeValue>) -> InnerRuntimeResult {
+		use RuntimeValue::*;
+
+		match self {
+			Function(builtin) => builtin.call(env, &args),
+			Constructor(ty, variant) => {
+				let arity = ty.variants[*variant].1.len();
+				if args.len() != arity {
+					return Err(RuntimeError::WrongArgumentCount(
+						Box::from(ty.variant_name(*variant)),
+						arity,
+						args.len(),
+					));
+				}
+				Ok(Variant(VariantValue {
+					ty: Rc::clone(ty),
+					variant: *variant,
+					payload: args,
+				}))
+			}
+			other => Err(RuntimeError::NotCallable(other.to_owned())),
+		}
+	}
+
+	/// Conditions are numbers, where zero is false.
+	pub fn is_truthy(&self) -> Result<bool, RuntimeError> {
+		match self {
+			RuntimeValue::Number(number) => Ok(number.to_f64() != 0.0),
+			other => Err(RuntimeError::NotACondition(other.to_owned())),
+		}
+	}
+
+	/// Orders two values for a relational `operator`, `None` if they are
+	/// unordered, as NaN is.
+	pub fn compare(
+		&self,
+		rhs: &RuntimeValue,
+		operator: BinaryOp,
+	) -> Result<Option<Ordering>, RuntimeError> {
+		use RuntimeError::*;
+		use RuntimeOperation::Binary;
+		use RuntimeValue::*;
+
+		match (self, rhs) {
+			(Number(l), Number(r)) => Ok(l.compare(r)?),
+			(Char(l), Char(r)) => Ok(Some(l.cmp(r))),
+			// UTF-8 byte order is code point order
+			(String(l), String(r)) => Ok(Some(l.cmp(r))),
+			(left, right) => Err(UnsupportedOperation(Binary(
+				left.to_owned(),
+				operator,
+				right.to_owned(),
+			))),
+		}
+	}
 }
 
 impl Pos for &RuntimeValue {
@@ -47,12 +235,8 @@ impl Pos for &RuntimeValue {
 		use RuntimeValue::*;
 
 		match self {
-			Unit => Err(UnsupportedOperation(Unary(UnaryOp::Plus, Unit))),
 			number @ Number(_) => Ok(number.to_owned()),
-			string @ String(_) => Err(UnsupportedOperation(Unary(
-				UnaryOp::Plus,
-				string.to_owned(),
-			))),
+			other => Err(UnsupportedOperation(Unary(UnaryOp::Plus, other.to_owned()))),
 		}
 	}
 }
@@ -65,11 +249,10 @@ impl Neg for &RuntimeValue {
 		use RuntimeValue::*;
 
 		match self {
-			Unit => Err(UnsupportedOperation(Unary(UnaryOp::Plus, Unit))),
 			Number(number) => Ok(Number(number.to_owned().neg())),
-			string @ String(_) => Err(UnsupportedOperation(Unary(
-				UnaryOp::Plus,
-				string.to_owned(),
+			other => Err(UnsupportedOperation(Unary(
+				UnaryOp::Minus,
+				other.to_owned(),
 			))),
 		}
 	}
@@ -83,22 +266,16 @@ impl Add<&RuntimeValue> for &RuntimeValue {
 		use RuntimeValue::*;
 
 		match (self, rhs) {
-			(Number(l), Number(r)) => Ok(Number(l.to_owned().add(r.to_owned()))),
-			(number @ Number(_), other) => Err(UnsupportedOperation(Binary(
-				number.to_owned(),
-				BinaryOp::Add,
-				other.to_owned(),
-			))),
-			(Unit, other) => Err(UnsupportedOperation(Binary(
-				Unit,
-				BinaryOp::Add,
-				other.to_owned(),
-			))),
+			(Number(l), Number(r)) => Ok(Number(l.to_owned().add(r.to_owned())?)),
 			(String(l), String(r)) => Ok(String(format!("{l}{r}"))),
-			(string @ String(_), other) => Err(UnsupportedOperation(Binary(
-				string.to_owned(),
+			(String(l), Char(r)) => Ok(String(format!("{l}{r}"))),
+			(Char(l), String(r)) => Ok(String(format!("{l}{r}"))),
+			(Char(l), Char(r)) => Ok(String(format!("{l}{r}"))),
+			(Bytes(l), Bytes(r)) => Ok(Bytes([l.as_slice(), r].concat())),
+			(left, right) => Err(UnsupportedOperation(Binary(
+				left.to_owned(),
 				BinaryOp::Add,
-				other.to_owned(),
+				right.to_owned(),
 			))),
 		}
 	}
@@ -112,21 +289,11 @@ impl Sub<&RuntimeValue> for &RuntimeValue {
 		use RuntimeValue::*;
 
 		match (self, rhs) {
-			(Number(l), Number(r)) => Ok(Number(l.to_owned().sub(r.to_owned()))),
-			(number @ Number(_), other) => Err(UnsupportedOperation(Binary(
-				number.to_owned(),
+			(Number(l), Number(r)) => Ok(Number(l.to_owned().sub(r.to_owned())?)),
+			(left, right) => Err(UnsupportedOperation(Binary(
+				left.to_owned(),
 				BinaryOp::Subtract,
-				other.to_owned(),
-			))),
-			(Unit, other) => Err(UnsupportedOperation(Binary(
-				Unit,
-				BinaryOp::Subtract,
-				other.to_owned(),
-			))),
-			(string @ String(_), other) => Err(UnsupportedOperation(Binary(
-				string.to_owned(),
-				BinaryOp::Subtract,
-				other.to_owned(),
+				right.to_owned(),
 			))),
 		}
 	}
@@ -140,32 +307,16 @@ impl Mul<&RuntimeValue> for &RuntimeValue {
 		use RuntimeValue::*;
 
 		match (self, rhs) {
-			(Number(l), Number(r)) => Ok(Number(l.to_owned().mul(r.to_owned()))),
-			(number @ Number(_), other) => Err(UnsupportedOperation(Binary(
-				number.to_owned(),
-				BinaryOp::Multiply,
-				other.to_owned(),
-			))),
-			(Unit, other) => Err(UnsupportedOperation(Binary(
-				Unit,
-				BinaryOp::Multiply,
-				other.to_owned(),
-			))),
-			(string @ String(st), other @ &Number(Numeric::Int(i))) => {
-				if i < 0 || i > usize::MAX as i128 {
-					Err(UnsupportedOperation(Binary(
-						string.to_owned(),
-						BinaryOp::Multiply,
-						other.to_owned(),
-					)))
-				} else {
-					Ok(String(st.repeat(i as usize)))
-				}
+			(Number(l), Number(r)) => Ok(Number(l.to_owned().mul(r.to_owned())?)),
+			(String(st), &Number(Numeric::Int(i))) if i >= 0 && i <= usize::MAX as i128 => {
+				checked_repeat(st, i as usize)
+					.map(String)
+					.ok_or_else(|| InvalidArgument(Box::from("*"), rhs.to_owned()))
 			}
-			(string @ String(_), other) => Err(UnsupportedOperation(Binary(
-				string.to_owned(),
+			(left, right) => Err(UnsupportedOperation(Binary(
+				left.to_owned(),
 				BinaryOp::Multiply,
-				other.to_owned(),
+				right.to_owned(),
 			))),
 		}
 	}
@@ -179,50 +330,172 @@ impl Div<&RuntimeValue> for &RuntimeValue {
 		use RuntimeValue::*;
 
 		match (self, rhs) {
-			(Number(l), Number(r)) => Ok(Number(l.to_owned().div(r.to_owned()))),
-			(number @ Number(_), other) => Err(UnsupportedOperation(Binary(
-				number.to_owned(),
+			(Number(l), Number(r)) => Ok(Number(l.to_owned().div(r.to_owned())?)),
+			(left, right) => Err(UnsupportedOperation(Binary(
+				left.to_owned(),
 				BinaryOp::Divide,
-				other.to_owned(),
-			))),
-			(Unit, other) => Err(UnsupportedOperation(Binary(
-				Unit,
-				BinaryOp::Divide,
-				other.to_owned(),
-			))),
-			(string @ String(_), other) => Err(UnsupportedOperation(Binary(
-				string.to_owned(),
-				BinaryOp::Divide,
-				other.to_owned(),
+				right.to_owned(),
 			))),
 		}
 	}
 }
 
-impl Rem<&RuntimeValue> for &RuntimeValue {
+impl FloorDiv<&RuntimeValue> for &RuntimeValue {
 	type Output = InnerRuntimeResult;
-	fn rem(self, rhs: &RuntimeValue) -> Self::Output {
+	fn floor_div(self, rhs: &RuntimeValue) -> Self::Output {
 		use RuntimeError::*;
 		use RuntimeOperation::Binary;
 		use RuntimeValue::*;
 
 		match (self, rhs) {
-			(Number(l), Number(r)) => Ok(Number(l.to_owned().rem(r.to_owned()))),
-			(number @ Number(_), other) => Err(UnsupportedOperation(Binary(
-				number.to_owned(),
-				BinaryOp::Modulo,
-				other.to_owned(),
+			(Number(l), Number(r)) => Ok(Number(l.to_owned().floor_div(r.to_owned())?)),
+			(left, right) => Err(UnsupportedOperation(Binary(
+				left.to_owned(),
+				BinaryOp::FloorDivide,
+				right.to_owned(),
 			))),
-			(Unit, other) => Err(UnsupportedOperation(Binary(
-				Unit,
+		}
+	}
+}
+
+impl Modulo<&RuntimeValue> for &RuntimeValue {
+	type Output = InnerRuntimeResult;
+	fn modulo(self, rhs: &RuntimeValue) -> Self::Output {
+		use RuntimeError::*;
+		use RuntimeOperation::Binary;
+		use RuntimeValue::*;
+
+		match (self, rhs) {
+			(Number(l), Number(r)) => Ok(Number(l.to_owned().modulo(r.to_owned())?)),
+			(left, right) => Err(UnsupportedOperation(Binary(
+				left.to_owned(),
 				BinaryOp::Modulo,
-				other.to_owned(),
+				right.to_owned(),
 			))),
-			(string @ String(_), other) => Err(UnsupportedOperation(Binary(
-				string.to_owned(),
-				BinaryOp::Modulo,
-				other.to_owned(),
+		}
+	}
+}
+
+impl Pow<&RuntimeValue> for &RuntimeValue {
+	type Output = InnerRuntimeResult;
+	fn pow(self, rhs: &RuntimeValue) -> Self::Output {
+		use RuntimeError::*;
+		use RuntimeOperation::Binary;
+		use RuntimeValue::*;
+
+		match (self, rhs) {
+			(Number(l), Number(r)) => Ok(Number(l.to_owned().pow(r.to_owned())?)),
+			(left, right) => Err(UnsupportedOperation(Binary(
+				left.to_owned(),
+				BinaryOp::Power,
+				right.to_owned(),
 			))),
 		}
 	}
 }
+
+impl Not for &RuntimeValue {
+	type Output = InnerRuntimeResult;
+	fn not(self) -> Self::Output {
+		use RuntimeError::*;
+		use RuntimeOperation::Unary;
+		use RuntimeValue::*;
+
+		match self {
+			Number(number) => number.to_owned().not().map(Number),
+			_ => None,
+		}
+		.ok_or_else(|| UnsupportedOperation(Unary(UnaryOp::BitNot, self.to_owned())))
+	}
+}
+
+impl BitAnd<&RuntimeValue> for &RuntimeValue {
+	type Output = InnerRuntimeResult;
+	fn bitand(self, rhs: &RuntimeValue) -> Self::Output {
+		use RuntimeError::*;
+		use RuntimeOperation::Binary;
+		use RuntimeValue::*;
+
+		match (self, rhs) {
+			(Number(l), Number(r)) => l.to_owned().bitand(r.to_owned()).map(Number),
+			_ => None,
+		}
+		.ok_or_else(|| {
+			UnsupportedOperation(Binary(self.to_owned(), BinaryOp::BitAnd, rhs.to_owned()))
+		})
+	}
+}
+
+impl BitOr<&RuntimeValue> for &RuntimeValue {
+	type Output = InnerRuntimeResult;
+	fn bitor(self, rhs: &RuntimeValue) -> Self::Output {
+		use RuntimeError::*;
+		use RuntimeOperation::Binary;
+		use RuntimeValue::*;
+
+		match (self, rhs) {
+			(Number(l), Number(r)) => l.to_owned().bitor(r.to_owned()).map(Number),
+			_ => None,
+		}
+		.ok_or_else(|| {
+			UnsupportedOperation(Binary(self.to_owned(), BinaryOp::BitOr, rhs.to_owned()))
+		})
+	}
+}
+
+impl BitXor<&RuntimeValue> for &RuntimeValue {
+	type Output = InnerRuntimeResult;
+	fn bitxor(self, rhs: &RuntimeValue) -> Self::Output {
+		use RuntimeError::*;
+		use RuntimeOperation::Binary;
+		use RuntimeValue::*;
+
+		match (self, rhs) {
+			(Number(l), Number(r)) => l.to_owned().bitxor(r.to_owned()).map(Number),
+			_ => None,
+		}
+		.ok_or_else(|| {
+			UnsupportedOperation(Binary(self.to_owned(), BinaryOp::BitXor, rhs.to_owned()))
+		})
+	}
+}
+
+impl Shl<&RuntimeValue> for &RuntimeValue {
+	type Output = InnerRuntimeResult;
+	fn shl(self, rhs: &RuntimeValue) -> Self::Output {
+		use RuntimeError::*;
+		use RuntimeOperation::Binary;
+		use RuntimeValue::*;
+
+		let shifted = match (self, rhs) {
+			(Number(l), Number(r)) => l.to_owned().shl(r.to_owned()),
+			_ => None,
+		}
+		.ok_or_else(|| {
+			UnsupportedOperation(Binary(self.to_owned(), BinaryOp::ShiftLeft, rhs.to_owned()))
+		})?;
+		Ok(Number(shifted?))
+	}
+}
+
+impl Shr<&RuntimeValue> for &RuntimeValue {
+	type Output = InnerRuntimeResult;
+	fn shr(self, rhs: &RuntimeValue) -> Self::Output {
+		use RuntimeError::*;
+		use RuntimeOperation::Binary;
+		use RuntimeValue::*;
+
+		let shifted = match (self, rhs) {
+			(Number(l), Number(r)) => l.to_owned().shr(r.to_owned()),
+			_ => None,
+		}
+		.ok_or_else(|| {
+			UnsupportedOperation(Binary(
+				self.to_owned(),
+				BinaryOp::ShiftRight,
+				rhs.to_owned(),
+			))
+		})?;
+		Ok(Number(shifted?))
+	}
+}
@@ -1,10 +1,12 @@
 use std::{
 	cell::{Ref, RefCell},
 	fmt::{Debug, Display},
-	ops::{Add, Div, Mul, Neg, Rem, Sub},
+	ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Shl, Shr, Sub},
 	rc::Rc,
 };
 
+use num_traits::Pow;
+
 use crate::numeric::Numeric;
 
 use super::{value::RuntimeValue, RuntimeResult};
@@ -14,6 +16,16 @@ pub trait Pos {
 	fn pos(self) -> Self::Output;
 }
 
+pub trait FloorDiv<Rhs = Self> {
+	type Output;
+	fn floor_div(self, rhs: Rhs) -> Self::Output;
+}
+
+pub trait Modulo<Rhs = Self> {
+	type Output;
+	fn modulo(self, rhs: Rhs) -> Self::Output;
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct RuntimeVariable {
 	value: Rc<RefCell<RuntimeValue>>,
@@ -33,7 +45,7 @@ impl RuntimeVariable {
 	}
 
 	pub fn same_type(&self, other: &Self) -> bool {
-		self.inner().discriminant() == other.inner().discriminant()
+		self.inner().same_type(&other.inner())
 	}
 
 	pub fn number(number: Numeric) -> Self {
@@ -49,6 +61,12 @@ impl RuntimeVariable {
 	}
 }
 
+impl From<RuntimeValue> for RuntimeVariable {
+	fn from(value: RuntimeValue) -> Self {
+		Self::new(value)
+	}
+}
+
 impl Display for RuntimeVariable {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		write!(
@@ -102,9 +120,67 @@ impl Div<RuntimeVariable> for RuntimeVariable {
 	}
 }
 
-impl Rem<RuntimeVariable> for RuntimeVariable {
+impl FloorDiv<RuntimeVariable> for RuntimeVariable {
+	type Output = RuntimeResult;
+	fn floor_div(self, rhs: RuntimeVariable) -> Self::Output {
+		self.inner()
+			.floor_div(&rhs.inner())
+			.map(RuntimeVariable::new)
+	}
+}
+
+impl Modulo<RuntimeVariable> for RuntimeVariable {
+	type Output = RuntimeResult;
+	fn modulo(self, rhs: RuntimeVariable) -> Self::Output {
+		self.inner().modulo(&rhs.inner()).map(RuntimeVariable::new)
+	}
+}
+
+impl Pow<RuntimeVariable> for RuntimeVariable {
+	type Output = RuntimeResult;
+	fn pow(self, rhs: RuntimeVariable) -> Self::Output {
+		self.inner().pow(&rhs.inner()).map(RuntimeVariable::new)
+	}
+}
+
+impl Not for RuntimeVariable {
+	type Output = RuntimeResult;
+	fn not(self) -> Self::Output {
+		self.inner().not().map(RuntimeVariable::new)
+	}
+}
+
+impl BitAnd<RuntimeVariable> for RuntimeVariable {
+	type Output = RuntimeResult;
+	fn bitand(self, rhs: RuntimeVariable) -> Self::Output {
+		self.inner().bitand(&rhs.inner()).map(RuntimeVariable::new)
+	}
+}
+
+impl BitOr<RuntimeVariable> for RuntimeVariable {
+	type Output = RuntimeResult;
+	fn bitor(self, rhs: RuntimeVariable) -> Self::Output {
+		self.inner().bitor(&rhs.inner()).map(RuntimeVariable::new)
+	}
+}
+
+impl BitXor<RuntimeVariable> for RuntimeVariable {
+	type Output = RuntimeResult;
+	fn bitxor(self, rhs: RuntimeVariable) -> Self::Output {
+		self.inner().bitxor(&rhs.inner()).map(RuntimeVariable::new)
+	}
+}
+
+impl Shl<RuntimeVariable> for RuntimeVariable {
+	type Output = RuntimeResult;
+	fn shl(self, rhs: RuntimeVariable) -> Self::Output {
+		self.inner().shl(&rhs.inner()).map(RuntimeVariable::new)
+	}
+}
+
+impl Shr<RuntimeVariable> for RuntimeVariable {
 	type Output = RuntimeResult;
-	fn rem(self, rhs: RuntimeVariable) -> Self::Output {
-		self.inner().rem(&rhs.inner()).map(RuntimeVariable::new)
+	fn shr(self, rhs: RuntimeVariable) -> Self::Output {
+		self.inner().shr(&rhs.inner()).map(RuntimeVariable::new)
 	}
 }